@@ -14,3 +14,36 @@ macro_rules! err {
         return Err(err);
     }};
 }
+
+use super::structs::{Position, Token};
+
+/// The error result of a failed `&str -> Line<Token>` tokenization.
+#[derive(Debug)]
+pub enum TokenError {
+    /// A character with no defined meaning at this position.
+    InvalidChar(char, Position),
+    /// A quoted string literal that ran to end-of-line without a closing quote.
+    UnterminatedStringLiteral(String, Position),
+    /// Leading whitespace switched between tabs and spaces partway through a line.
+    InconsistentLeadingWhitespaceChars(Position),
+}
+
+/// The error result of a failed `Line<Token> -> ExprLine` conversion.
+#[derive(Debug)]
+pub enum ExprConversionFailure {
+    /// A token that cannot legally begin (or continue) an expression.
+    InvalidToken(Token, String, Position),
+    /// The line ended where another token was still expected.
+    /// Message describes the expected token.
+    UnexpectedLastToken(Token, String, Position),
+    /// A token that is recognized but not yet handled.
+    ToDo(Token),
+}
+
+/// The error result of a failed `&str -> ExprLine` conversion, wrapping
+/// whichever of the two stages (tokenizing or expression conversion) failed.
+#[derive(Debug)]
+pub enum LineConversionFailure {
+    TokenFailure(TokenError),
+    ExprFailure(ExprConversionFailure),
+}
@@ -5,20 +5,70 @@ use std::iter::Peekable;
 use crate::debug;
 use crate::err;
 use core::fmt::Write as _;
-pub use token::{Operator, Token, TokenError};
+pub use token::{Keyword, Operator, Span, Spanned, Token, TokenError};
+
+/// Thin wrapper around a `Peekable<Chars>` that tracks the (1-based) line
+/// and column of the most recently consumed character, so that tokens and
+/// errors can be tagged with a [`Span`].
+struct Cursor<I: Iterator<Item = char>> {
+    chars: Peekable<I>,
+    line: u32,
+    col: u32,
+}
+
+impl<I: Iterator<Item = char>> Cursor<I> {
+    fn new(chars: Peekable<I>) -> Self {
+        Self { chars, line: 1, col: 0 }
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let ch = self.chars.next()?;
+        if ch == '\n' {
+            self.line += 1;
+            self.col = 0;
+        } else {
+            self.col += 1;
+        }
+        Some(ch)
+    }
+
+    fn peek(&mut self) -> Option<&char> {
+        self.chars.peek()
+    }
+
+    fn next_if(&mut self, func: impl FnOnce(&char) -> bool) -> Option<char> {
+        let ch = self.chars.next_if(func)?;
+        if ch == '\n' {
+            self.line += 1;
+            self.col = 0;
+        } else {
+            self.col += 1;
+        }
+        Some(ch)
+    }
+
+    fn next_if_eq(&mut self, expected: &char) -> Option<char> {
+        self.next_if(|c| c == expected)
+    }
+
+    /// A zero-width span sitting at the cursor's current position.
+    fn point(&self) -> Span {
+        Span { line: self.line, col_start: self.col, col_end: self.col }
+    }
+}
 
 /// Repeatedly writes to a buffer using new characters from `chars`
 /// so long as said chars satisfy a given predicate function.
 fn predicated_char_writes(
     mut buf: String,
-    chars: &mut Peekable<impl Iterator<Item = char>>,
+    chars: &mut Cursor<impl Iterator<Item = char>>,
     pred: impl Fn(&char) -> bool,
 ) -> Result<String, TokenError> {
     while let Some(mut ch) = chars.next_if(&pred) {
         if ch == '\\' {
             match chars.next() {
                 Some(c) => ch = c,
-                None => err!(TokenError::UnterminatedStringLiteral(buf)),
+                None => err!(TokenError::UnterminatedStringLiteral(buf, chars.point())),
             }
         };
 
@@ -30,10 +80,13 @@ fn predicated_char_writes(
 
 fn parse_char(
     ch: char,
-    chars: &mut Peekable<impl Iterator<Item = char>>,
-) -> Result<Option<Token>, TokenError> {
+    chars: &mut Cursor<impl Iterator<Item = char>>,
+) -> Result<Option<Spanned<Token>>, TokenError> {
     use Operator::*;
     use Token::*;
+
+    let start = Span { line: chars.line, col_start: chars.col, col_end: chars.col };
+
     let token = match ch {
         '#' => {
             // consume every subsequent char until hitting next line
@@ -50,6 +103,18 @@ fn parse_char(
         '-' => Op(Minus),
         '+' => Op(Plus),
         '%' => Op(Mod),
+        // the doubled char (`&&`/`||`) is checked first, so that the logical
+        // connectives aren't mistaken for their bitwise counterparts
+        '&' => match chars.next_if_eq(&'&') {
+            Some(_) => Op(And),
+            None => Op(BitAnd),
+        },
+        '|' => match chars.next_if_eq(&'|') {
+            Some(_) => Op(Or),
+            None => Op(BitOr),
+        },
+        '^' => Op(BitXor),
+        '~' => Op(BitNot),
         '!' => match chars.next_if_eq(&'=') {
             Some(_) => Op(NotEqual),
             None => Op(Bang),
@@ -58,23 +123,57 @@ fn parse_char(
             Some(_) => Op(EqualTo),
             None => Op(EqualsSign),
         },
-        '<' => match chars.next_if_eq(&'=') {
-            Some(_) => Op(LessThanEqual),
-            None => Op(LeftAngle),
+        // the doubled char (`<<`) is checked before `=` (`<=`), so that
+        // `<<=` (not yet a token) still lexes as `Shl` followed by `EqualsSign`
+        '<' => match chars.next_if_eq(&'<') {
+            Some(_) => Op(Shl),
+            None => match chars.next_if_eq(&'=') {
+                Some(_) => Op(LessThanEqual),
+                None => Op(LeftAngle),
+            },
         },
-        '>' => match chars.next_if_eq(&'=') {
-            Some(_) => Op(GreaterThanEqual),
-            None => Op(RightAngle),
+        '>' => match chars.next_if_eq(&'>') {
+            Some(_) => Op(Shr),
+            None => match chars.next_if_eq(&'=') {
+                Some(_) => Op(GreaterThanEqual),
+                None => Op(RightAngle),
+            },
         },
         '\'' | '"' => {
             let buf = predicated_char_writes(String::new(), chars, |c| c != &ch)?;
             match chars.next() {
                 // asserting that the next char is in fact the correct one
                 Some(c) if c == ch => Token::StringLiteral(buf),
-                _ => err!(TokenError::UnterminatedStringLiteral(buf)),
+                _ => err!(TokenError::UnterminatedStringLiteral(buf, chars.point())),
             }
         }
         c if c.is_whitespace() => return Ok(None),
+        // a leading `0x`/`0b`/`0o` switches to parsing a non-decimal integer literal
+        '0' if matches!(chars.peek(), Some('x' | 'X' | 'b' | 'B' | 'o' | 'O')) => {
+            let radix = match chars.next() {
+                Some('x' | 'X') => 16,
+                Some('b' | 'B') => 2,
+                Some('o' | 'O') => 8,
+                _ => unreachable!("peeked above"),
+            };
+            let pred: fn(&char) -> bool = match radix {
+                16 => |c: &char| c.is_ascii_hexdigit(),
+                2 => |c: &char| *c == '0' || *c == '1',
+                _ => |c: &char| ('0'..='7').contains(c),
+            };
+            let buf = predicated_char_writes(String::new(), chars, pred)?;
+            let span = Span { line: start.line, col_start: start.col_start, col_end: chars.col };
+            if buf.is_empty() {
+                err!(TokenError::InvalidNumericLiteral(
+                    "expected at least one digit after numeric prefix".into(),
+                    span,
+                ));
+            }
+            match u32::from_str_radix(&buf, radix).ok().and_then(|n| u16::try_from(n).ok()) {
+                Some(n) => Token::Int(n),
+                None => err!(TokenError::InvalidNumericLiteral(buf, span)),
+            }
+        }
         // parsing Token::Num
         n if n.is_numeric() => {
             // take all subsequent numeric chars
@@ -91,26 +190,34 @@ fn parse_char(
         }
         // parsing identifiers as a buffer of any alphanumeric string following an alpha
         c if c == '_' || c.is_alphabetic() => {
-            Identifier(predicated_char_writes(String::from(c), chars, |c| {
+            let buf = predicated_char_writes(String::from(c), chars, |c| {
                 c == &'_' || c.is_alphanumeric()
-            })?)
+            })?;
+            // `use Token::*` above shadows the `Keyword` type with its
+            // same-named tuple variant, so this has to go through the
+            // fully qualified path.
+            match token::Keyword::lookup(&buf) {
+                Some(kw) => Token::Keyword(kw),
+                None => Identifier(buf),
+            }
         }
-        c => err!(TokenError::InvalidChar(c)),
+        c => err!(TokenError::InvalidChar(c, start)),
     };
 
-    Ok(Some(token))
+    let span = Span { line: start.line, col_start: start.col_start, col_end: chars.col };
+    Ok(Some(Spanned { node: token, span }))
 }
 
 /// Special parsing case for `\n`. TODO: revise entirely
 fn parse_newline(
     indent: &mut (usize, char, usize),
-    chars: &mut Peekable<impl Iterator<Item = char>>,
-    tokens: &mut Vec<Token>,
+    chars: &mut Cursor<impl Iterator<Item = char>>,
+    tokens: &mut Vec<Spanned<Token>>,
 ) -> Result<(), TokenError> {
     if tokens.len() != 0 {
-        let token = Token::Op(Operator::NewLine);
+        let token = Spanned { node: Token::Op(Operator::NewLine), span: chars.point() };
         #[cfg(debug_assertions)]
-        debug!("Pushing token: {token:?}");
+        debug!("Pushing token: {:?}", token.node);
         tokens.push(token)
     };
 
@@ -136,26 +243,26 @@ fn parse_newline(
         };
 
         if indent_chars.iter().any(|c| c != &indent.1) {
-            err!(TokenError::InconsistentLeadingWhitespaceChars);
+            err!(TokenError::InconsistentLeadingWhitespaceChars(chars.point()));
         };
 
         if indent_len == 0 {
             break 'c;
         } else if indent_len > indent.0 {
-            let token = Token::Indent;
+            let token = Spanned { node: Token::Indent, span: chars.point() };
             #[cfg(debug_assertions)]
-            debug!("Pushing token: {token:?}");
+            debug!("Pushing token: {:?}", token.node);
             tokens.push(token);
             indent.0 = indent_len
         } else if indent_len < indent.0 {
             if indent_len % indent.2 != 0 {
-                err!(TokenError::InconsistentLeadingWhitespaceChars)
+                err!(TokenError::InconsistentLeadingWhitespaceChars(chars.point()))
             }
 
             while indent.0 != indent_len {
-                let token = Token::Dedent;
+                let token = Spanned { node: Token::Dedent, span: chars.point() };
                 #[cfg(debug_assertions)]
-                debug!("Pushing token: {token:?}");
+                debug!("Pushing token: {:?}", token.node);
                 tokens.push(token);
 
                 indent.0 -= indent.2;
@@ -166,14 +273,15 @@ fn parse_newline(
 }
 
 pub(crate) fn parse_chars(
-    mut chars: Peekable<impl Iterator<Item = char>>,
-) -> Result<Vec<Token>, TokenError> {
+    chars: Peekable<impl Iterator<Item = char>>,
+) -> Result<Vec<Spanned<Token>>, TokenError> {
     // Since we check for indent levels after finding a newline,
     // but also since we want to check for indent levels on the
     // first iteration, it becomes necessary to pretend that the
     // first given character is a newline
     let mut ch = '\n';
     let mut resp = Vec::new();
+    let mut chars = Cursor::new(chars);
 
     // information about current indent level
     // indent.0: last indent level
@@ -186,7 +294,7 @@ pub(crate) fn parse_chars(
             parse_newline(&mut indent, &mut chars, &mut resp)?;
         } else if let Some(token) = parse_char(ch, &mut chars)? {
             #[cfg(debug_assertions)]
-            debug!("Pushing token: {token:?}");
+            debug!("Pushing token: {:?}", token.node);
             resp.push(token);
         }
 
@@ -198,3 +306,58 @@ pub(crate) fn parse_chars(
 
     Ok(resp)
 }
+
+/// Like [`parse_chars`], but never bails out on the first bad character.
+/// Every [`TokenError`] encountered is recorded and lexing resumes from a
+/// safe resynchronization point, so a single typo doesn't mask every later
+/// problem in the document.
+///
+/// Resync rule: an unterminated string literal skips to the end of the
+/// current line; any other error skips to the next whitespace or newline.
+/// Either way at least the offending character is consumed, guaranteeing
+/// forward progress.
+pub(crate) fn parse_chars_recovering(
+    chars: Peekable<impl Iterator<Item = char>>,
+) -> (Vec<Spanned<Token>>, Vec<TokenError>) {
+    let mut ch = '\n';
+    let mut resp = Vec::new();
+    let mut errors = Vec::new();
+    let mut chars = Cursor::new(chars);
+    let mut indent = (0, '_', 0);
+
+    loop {
+        if ch == '\n' {
+            if let Err(e) = parse_newline(&mut indent, &mut chars, &mut resp) {
+                // the leading whitespace for this line was already consumed
+                // by `parse_newline` before it errored, so simply recording
+                // the error and continuing is itself a safe resync point
+                errors.push(e);
+            }
+        } else {
+            match parse_char(ch, &mut chars) {
+                Ok(Some(token)) => {
+                    #[cfg(debug_assertions)]
+                    debug!("Pushing token: {:?}", token.node);
+                    resp.push(token);
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    let resync_to_eol = matches!(e, TokenError::UnterminatedStringLiteral(..));
+                    errors.push(e);
+                    if resync_to_eol {
+                        while chars.next_if(|c| c != &'\n').is_some() {}
+                    } else {
+                        while chars.next_if(|c| !c.is_whitespace()).is_some() {}
+                    }
+                }
+            }
+        }
+
+        match chars.next() {
+            Some(c) => ch = c,
+            None => break,
+        }
+    }
+
+    (resp, errors)
+}
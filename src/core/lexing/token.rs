@@ -1,5 +1,37 @@
 use std::fmt::Display;
 
+/// A range of columns on a single (1-based) source line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Span {
+    pub line: u32,
+    pub col_start: u32,
+    pub col_end: u32,
+}
+
+impl Display for Span {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, col {}-{}", self.line, self.col_start, self.col_end)
+    }
+}
+
+/// A value paired with the span of source it was lexed from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spanned<T> {
+    pub node: T,
+    pub span: Span,
+}
+
+/// Miscellaneous one or two char constants.
+///
+/// Precedence (loosest to tightest binding), for a later parser stage to
+/// rely on:
+/// 1. `And`/`Or` (short-circuit logical connectives)
+/// 2. relational operators (`EqualTo`, `LessThanEqual`, `LeftAngle`, ...)
+/// 3. `BitAnd`/`BitOr`/`BitXor`
+/// 4. `Shl`/`Shr`
+/// 5. `Plus`/`Minus`
+/// 6. `Asterisk`/`Slash`/`Mod`
+/// 7. unary `Bang`/`BitNot`
 #[derive(Debug, PartialEq)]
 pub enum Operator {
     Dollar,
@@ -25,6 +57,68 @@ pub enum Operator {
     EqualTo,
     GreaterThanEqual,
     LessThanEqual,
+
+    BitAnd,
+    BitOr,
+    BitXor,
+    BitNot,
+    Shl,
+    Shr,
+
+    /// Short-circuit logical AND (`&&`), distinct from the bitwise `BitAnd`.
+    And,
+    /// Short-circuit logical OR (`||`), distinct from the bitwise `BitOr`.
+    Or,
+}
+
+/// A reserved word that a run of identifier characters may promote to,
+/// instead of falling through to a plain [`Token::Identifier`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Keyword {
+    If,
+    Elif,
+    Else,
+    End,
+    True,
+    False,
+    And,
+    Or,
+    Not,
+}
+
+impl Keyword {
+    /// Looks up a reserved word by its exact (case-sensitive) spelling.
+    /// Only fires when the whole identifier buffer matches, never a prefix.
+    pub fn lookup(s: &str) -> Option<Self> {
+        Some(match s {
+            "if" => Self::If,
+            "elif" => Self::Elif,
+            "else" => Self::Else,
+            "end" => Self::End,
+            "true" => Self::True,
+            "false" => Self::False,
+            "and" => Self::And,
+            "or" => Self::Or,
+            "not" => Self::Not,
+            _ => return None,
+        })
+    }
+}
+
+impl Display for Keyword {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::If => write!(f, "if"),
+            Self::Elif => write!(f, "elif"),
+            Self::Else => write!(f, "else"),
+            Self::End => write!(f, "end"),
+            Self::True => write!(f, "true"),
+            Self::False => write!(f, "false"),
+            Self::And => write!(f, "and"),
+            Self::Or => write!(f, "or"),
+            Self::Not => write!(f, "not"),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq)]
@@ -37,6 +131,10 @@ pub enum Token {
     Dedent,
     StringLiteral(String),
     Number(f32),
+    /// An integer literal written in a non-decimal base (`0x`/`0b`/`0o`).
+    Int(u16),
+    /// A reserved word, e.g. `if` or `true`.
+    Keyword(Keyword),
     Identifier(String),
 }
 
@@ -62,6 +160,16 @@ impl Display for Operator {
             Self::EqualTo => write!(f, "=="),
             Self::GreaterThanEqual => write!(f, ">="),
             Self::LessThanEqual => write!(f, "<="),
+
+            Self::BitAnd => write!(f, "&"),
+            Self::BitOr => write!(f, "|"),
+            Self::BitXor => write!(f, "^"),
+            Self::BitNot => write!(f, "~"),
+            Self::Shl => write!(f, "<<"),
+            Self::Shr => write!(f, ">>"),
+
+            Self::And => write!(f, "&&"),
+            Self::Or => write!(f, "||"),
         }
     }
 }
@@ -75,29 +183,32 @@ impl Display for Token {
             Self::Identifier(s) => write!(f, "{s}"),
             Self::StringLiteral(s) => write!(f, "\"{}\"", s.replace("\"", "\\\"")),
             Self::Number(num) => write!(f, "{num}"),
+            Self::Int(num) => write!(f, "{num}"),
+            Self::Keyword(kw) => write!(f, "{kw}"),
         }
     }
 }
 
 #[derive(Debug)]
 pub enum TokenError {
-    UnterminatedStringLiteral(String),
-    InvalidChar(char),
-    InconsistentLeadingWhitespaceChars,
-    InconsistentLeadingWhitespaceCount,
+    UnterminatedStringLiteral(String, Span),
+    InvalidChar(char, Span),
+    InconsistentLeadingWhitespaceChars(Span),
+    /// A `0x`/`0b`/`0o` literal with no digits, or whose value overflows `u16`.
+    InvalidNumericLiteral(String, Span),
 }
 
 impl std::fmt::Display for TokenError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::UnterminatedStringLiteral(s) => write!(f, "UnterminatedStringLiteral: {s}"),
-            Self::InvalidChar(s) => write!(f, "InvalidChar: {s}"),
-            Self::InconsistentLeadingWhitespaceChars => {
-                write!(f, "InconsistentLeadingWhitespaceChars")
+            Self::UnterminatedStringLiteral(s, span) => {
+                write!(f, "UnterminatedStringLiteral: {s} ({span})")
             }
-            Self::InconsistentLeadingWhitespaceCount => {
-                write!(f, "InconsistentLeadingWhitespaceCount")
+            Self::InvalidChar(s, span) => write!(f, "InvalidChar: {s} ({span})"),
+            Self::InconsistentLeadingWhitespaceChars(span) => {
+                write!(f, "InconsistentLeadingWhitespaceChars ({span})")
             }
+            Self::InvalidNumericLiteral(s, span) => write!(f, "InvalidNumericLiteral: {s} ({span})"),
         }
     }
 }
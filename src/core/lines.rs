@@ -1,8 +1,11 @@
 use core::fmt;
 
-use super::errors::LineConversionFailure;
-
-use super::{ExprLine, Token};
+use super::errors::{LineConversionFailure, TokenError};
+use super::parsing::ExprLine;
+use super::structs::{Position, Spanned, Token};
+#[cfg(debug_assertions)]
+use crate::debug;
+use crate::err;
 
 /// A single line.
 /// Usually should be either Line<Token> or Line<Expr>
@@ -14,13 +17,282 @@ pub struct Line<T> {
     pub(crate) members: Vec<T>,
 }
 
+impl TryFrom<&str> for Line<Spanned<Token>> {
+    type Error = TokenError;
+
+    /// Tokenizes a single line of source, tracking a column cursor as it
+    /// goes so that any `TokenError` (and any later `ExprConversionFailure`
+    /// built from one of these tokens) can report where on the line it
+    /// occurred. The line number itself is left at `0` (unknown): this
+    /// function only ever sees one line at a time, so whoever is iterating
+    /// a whole document is responsible for filling it in.
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        use std::fmt::Write as _;
+        use Token::*;
+
+        let mut chars = value.trim_end().chars().peekable();
+        let mut col: u16 = 0;
+
+        macro_rules! next_char {
+            () => {{
+                let ch = chars.next();
+                if ch.is_some() {
+                    col += 1;
+                }
+                ch
+            }};
+        }
+
+        let mut ch = match next_char!() {
+            Some(c) => c,
+            None => {
+                return Ok(Line {
+                    total_whitespace: 0,
+                    members: vec![],
+                })
+            }
+        };
+
+        // loop over the first couple characters and check for whitespace total/consistency
+        let whitespace_char = ch;
+        let mut total_whitespace: u8 = 0;
+        while ch.is_whitespace() {
+            if ch != whitespace_char {
+                err!(TokenError::InconsistentLeadingWhitespaceChars(
+                    Position::new(0, col)
+                ));
+            }
+            total_whitespace += 1;
+            ch = match next_char!() {
+                Some(c) => c,
+                None => {
+                    return Ok(Line {
+                        total_whitespace,
+                        members: vec![],
+                    })
+                }
+            };
+        }
+
+        let mut members: Vec<Spanned<Token>> = vec![];
+        let mut buf = String::new();
+        let mut buf_start_col: Option<u16> = None;
+
+        macro_rules! push_tok {
+            ($t:expr, $col:expr) => {
+                members.push(Spanned {
+                    node: $t,
+                    pos: Position::new(0, $col),
+                })
+            };
+        }
+
+        macro_rules! write_buf {
+            ($col:expr, $($arg:tt)*) => {{
+                if buf.is_empty() {
+                    buf_start_col = Some($col);
+                }
+                write!(buf, $($arg)*).expect("writing to string buffer")
+            }};
+        }
+
+        macro_rules! flush_buf {
+            () => {
+                if !buf.is_empty() {
+                    let taken = std::mem::take(&mut buf);
+                    let pos = Position::new(0, buf_start_col.take().expect("buf_start_col set alongside buf"));
+                    // `use Token::*` above shadows the `Keyword` type with
+                    // its same-named tuple variant, so this has to go
+                    // through the fully qualified path.
+                    members.push(Spanned {
+                        node: match super::structs::Keyword::lookup(&taken) {
+                            Some(kw) => Token::Keyword(kw),
+                            None => Ident(taken),
+                        },
+                        pos,
+                    });
+                }
+            };
+        }
+
+        loop {
+            let start_col = col;
+            match ch {
+                c if c.is_whitespace() => flush_buf!(),
+                '=' => {
+                    flush_buf!();
+                    match chars.peek() {
+                        Some('=') => {
+                            next_char!();
+                            push_tok!(EqualTo, start_col)
+                        }
+                        _ => push_tok!(Equals, start_col),
+                    }
+                }
+                '<' => {
+                    flush_buf!();
+                    match chars.peek() {
+                        // the doubled char is checked before `=`, so `<<` isn't
+                        // mistaken for the start of a `<=`
+                        Some('<') => {
+                            next_char!();
+                            push_tok!(Shl, start_col)
+                        }
+                        Some('=') => {
+                            next_char!();
+                            push_tok!(LessThanEqual, start_col)
+                        }
+                        _ => push_tok!(LeftAngle, start_col),
+                    }
+                }
+                '>' => {
+                    flush_buf!();
+                    match chars.peek() {
+                        Some('>') => {
+                            next_char!();
+                            push_tok!(Shr, start_col)
+                        }
+                        Some('=') => {
+                            next_char!();
+                            push_tok!(GreaterThanEqual, start_col)
+                        }
+                        _ => push_tok!(RightAngle, start_col),
+                    }
+                }
+                '&' => {
+                    flush_buf!();
+                    // the doubled char is checked first, so `&&` isn't
+                    // mistaken for the start of two separate `BitAnd`s
+                    match chars.peek() {
+                        Some('&') => {
+                            next_char!();
+                            push_tok!(And, start_col)
+                        }
+                        _ => push_tok!(BitAnd, start_col),
+                    }
+                }
+                '|' => {
+                    flush_buf!();
+                    match chars.peek() {
+                        Some('|') => {
+                            next_char!();
+                            push_tok!(Or, start_col)
+                        }
+                        _ => push_tok!(BitOr, start_col),
+                    }
+                }
+                '^' => {
+                    flush_buf!();
+                    push_tok!(BitXor, start_col)
+                }
+                '~' => {
+                    flush_buf!();
+                    push_tok!(BitNot, start_col)
+                }
+                '!' => {
+                    flush_buf!();
+                    match chars.peek() {
+                        Some('=') => {
+                            next_char!();
+                            push_tok!(NotEqual, start_col)
+                        }
+                        _ => push_tok!(Bang, start_col),
+                    }
+                }
+                '.' => {
+                    flush_buf!();
+                    push_tok!(Period, start_col)
+                }
+                ':' => {
+                    flush_buf!();
+                    push_tok!(Colon, start_col)
+                }
+                '$' => {
+                    flush_buf!();
+                    push_tok!(Dollar, start_col)
+                }
+                '(' => {
+                    flush_buf!();
+                    push_tok!(OpenBracket, start_col)
+                }
+                ')' => {
+                    flush_buf!();
+                    push_tok!(CloseBracket, start_col)
+                }
+                '/' => {
+                    flush_buf!();
+                    push_tok!(Div, start_col)
+                }
+                '*' => {
+                    flush_buf!();
+                    push_tok!(Mult, start_col)
+                }
+                '-' => {
+                    flush_buf!();
+                    push_tok!(Sub, start_col)
+                }
+                '+' => {
+                    flush_buf!();
+                    push_tok!(Add, start_col)
+                }
+                '%' => {
+                    flush_buf!();
+                    push_tok!(Mod, start_col)
+                }
+                '\'' | '"' => {
+                    let quote = ch;
+                    loop {
+                        ch = match next_char!() {
+                            Some(c) => c,
+                            None => {
+                                err!(TokenError::UnterminatedStringLiteral(
+                                    std::mem::take(&mut buf),
+                                    Position::new(0, start_col),
+                                ))
+                            }
+                        };
+                        ch = match ch {
+                            c if c == quote => break,
+                            '\\' => match next_char!() {
+                                Some(c) => c,
+                                None => {
+                                    err!(TokenError::UnterminatedStringLiteral(
+                                        std::mem::take(&mut buf),
+                                        Position::new(0, start_col),
+                                    ))
+                                }
+                            },
+                            c => c,
+                        };
+                        write_buf!(start_col, "{}", ch);
+                    }
+                    push_tok!(QuotedString(std::mem::take(&mut buf)), start_col);
+                }
+                '#' => break,
+                other => write_buf!(start_col, "{}", other),
+            };
+
+            ch = match next_char!() {
+                Some(c) => c,
+                None => break,
+            };
+        }
+        flush_buf!();
+
+        Ok(Line {
+            total_whitespace,
+            members,
+        })
+    }
+}
+
 impl TryFrom<&str> for ExprLine {
     type Error = LineConversionFailure;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
         use LineConversionFailure as Error;
 
-        let line = match Line::<Token>::try_from(value) {
+        let line = match Line::<Spanned<Token>>::try_from(value) {
             Ok(l) => l,
             Err(e) => return Err(Error::TokenFailure(e)),
         };
@@ -6,4 +6,10 @@ structs and parsing them into expressions.
 mod errors;
 pub(crate) mod lexing;
 
-pub use lexing::{Operator, Token, TokenError};
+pub use lexing::{Keyword, Operator, Span, Spanned, Token, TokenError};
+
+// A separate, still-experimental tokenizer/expression pipeline built around
+// `Line<T>` and positional diagnostics. Not yet wired into `parse_string`.
+mod lines;
+mod parsing;
+mod structs;
@@ -1,15 +1,17 @@
 #[cfg(debug_assertions)]
 use crate::debug;
 
-use super::{errors::ExprConversionFailure, Expr, Expr::*, Line, Token};
+use super::errors::ExprConversionFailure;
+use super::lines::Line;
+use super::structs::{Expr, Expr::*, Keyword, Spanned, Token};
 use crate::err;
 
 pub type ExprLine = Line<Expr>;
 
-impl TryFrom<Line<Token>> for ExprLine {
+impl TryFrom<Line<Spanned<Token>>> for ExprLine {
     type Error = ExprConversionFailure;
 
-    fn try_from(value: Line<Token>) -> Result<Self, Self::Error> {
+    fn try_from(value: Line<Spanned<Token>>) -> Result<Self, Self::Error> {
         use ExprConversionFailure::*;
 
         let mut resp = Self {
@@ -27,11 +29,9 @@ impl TryFrom<Line<Token>> for ExprLine {
 
         'expr_loop: loop {
             let resp = &mut resp.members;
+            let Spanned { node: tok, pos: token_pos } = token;
 
             macro_rules! push {
-                () => {
-                    push!(token);
-                };
                 ($expr:expr) => {
                     let push_res = $expr;
                     #[cfg(debug_assertions)]
@@ -42,16 +42,29 @@ impl TryFrom<Line<Token>> for ExprLine {
                 };
             }
 
-            let expr = match token {
+            let expr = match tok {
                 Token::Equals | Token::Period => {
                     err!(InvalidToken(
-                        token.to_owned(),
+                        tok.to_owned(),
                         "Incorrect token to start expression".into(),
+                        token_pos,
                     ))
                 }
-                Token::Bang => err!(ToDo(token.to_owned())),
+                Token::Bang => Not,
                 Token::Colon => Colon,
 
+                // `true`/`false` become `Int(1)`/`Int(0)` and the logical
+                // keywords map onto the Exprs of the same name; structural
+                // keywords (if/elif/else/end) have no Expr and fail as ToDo
+                Token::Keyword(Keyword::True) => Int(1),
+                Token::Keyword(Keyword::False) => Int(0),
+                Token::Keyword(Keyword::And) => And,
+                Token::Keyword(Keyword::Or) => Or,
+                Token::Keyword(Keyword::Not) => Not,
+                Token::Keyword(Keyword::If | Keyword::Elif | Keyword::Else | Keyword::End) => {
+                    err!(ToDo(tok.to_owned()))
+                }
+
                 Token::EqualTo => EqualTo,
                 Token::GreaterThanEqual => GreaterThanEqual,
                 Token::LessThanEqual => LessThanEqual,
@@ -59,6 +72,15 @@ impl TryFrom<Line<Token>> for ExprLine {
                 Token::LeftAngle => LessThan,
                 Token::RightAngle => GreaterThan,
 
+                Token::BitAnd => BitAnd,
+                Token::BitOr => BitOr,
+                Token::BitXor => BitXor,
+                Token::BitNot => BitNot,
+                Token::Shl => Shl,
+                Token::Shr => Shr,
+                Token::And => And,
+                Token::Or => Or,
+
                 Token::OpenBracket => OpenBracket,
                 Token::Div => Div,
                 Token::Mult => Mult,
@@ -67,22 +89,31 @@ impl TryFrom<Line<Token>> for ExprLine {
                 Token::Mod => Mod,
                 Token::CloseBracket => CloseBracket,
 
-                Token::Literal(s) => match tokens.next() {
+                Token::Ident(s) => match tokens.next() {
                     // handling for name='attr' expressions
-                    Some(Token::Equals) => {
+                    Some(Spanned { node: Token::Equals, .. }) => {
                         let val = match tokens.next() {
-                            Some(Token::Literal(s)) => s,
+                            Some(Spanned { node: Token::Ident(s), .. })
+                            | Some(Spanned { node: Token::QuotedString(s), .. }) => s,
                             Some(t) => {
-                                err!(InvalidToken(t, "expected string after equals sign".into()))
+                                err!(InvalidToken(
+                                    t.node,
+                                    "expected string after equals sign".into(),
+                                    t.pos,
+                                ))
                             }
-                            None => err!(UnexpectedLastToken(Token::Literal(s), "string".into())),
+                            None => err!(UnexpectedLastToken(
+                                Token::Ident(s),
+                                "string".into(),
+                                token_pos,
+                            )),
                         };
                         Attribute { key: s, val }
                     }
                     // with no subsequent token
                     None => match s.parse() {
                         Ok(n) => Int(n),
-                        Err(_) => Raw(s),
+                        Err(_) => Ident(s),
                     },
                     // in the case that the next token is just some irrelevant
                     // token, we will handle the first token before trying again
@@ -90,72 +121,116 @@ impl TryFrom<Line<Token>> for ExprLine {
                     Some(t) => {
                         push!(match s.parse() {
                             Ok(n) => Int(n),
-                            Err(_) => Raw(s),
+                            Err(_) => Ident(s),
                         });
                         token = t;
                         continue 'expr_loop;
                     }
                 },
 
+                Token::QuotedString(s) => match tokens.next() {
+                    None => QuotedString(s),
+                    Some(t) => {
+                        push!(QuotedString(s));
+                        token = t;
+                        continue 'expr_loop;
+                    }
+                },
+
                 Token::Dollar => {
                     // the object or selector
                     let src: String = match tokens.next() {
-                        Some(Token::Literal(s)) => s,
-                        Some(t) => err!(InvalidToken(t, "expected a string".into())),
-                        None => err!(UnexpectedLastToken(token, "string literal".into())),
+                        Some(Spanned { node: Token::Ident(s), .. })
+                        | Some(Spanned { node: Token::QuotedString(s), .. }) => s,
+                        Some(t) => err!(InvalidToken(t.node, "expected a string".into(), t.pos)),
+                        None => err!(UnexpectedLastToken(
+                            Token::Dollar,
+                            "string literal".into(),
+                            token_pos,
+                        )),
                     };
 
                     // argument for the selector or None for the object
                     let arg: Option<String> = match tokens.next() {
-                        Some(Token::LeftAngle) => match tokens.next() {
+                        Some(Spanned { node: Token::LeftAngle, .. }) => match tokens.next() {
                             // case: $sel<...>.trait
-                            Some(Token::Literal(s)) => match tokens.next() {
-                                Some(Token::RightAngle) => match tokens.next() {
-                                    Some(Token::Period) => Some(s),
+                            Some(Spanned { node: Token::Ident(s), .. })
+                            | Some(Spanned { node: Token::QuotedString(s), .. }) => {
+                                match tokens.next() {
+                                    Some(Spanned { node: Token::RightAngle, .. }) => match tokens.next() {
+                                        Some(Spanned { node: Token::Period, .. }) => Some(s),
+                                        Some(t) => {
+                                            err!(InvalidToken(
+                                                t.node,
+                                                "expected a period".into(),
+                                                t.pos,
+                                            ))
+                                        }
+                                        None => err!(InvalidToken(
+                                            Token::Dollar,
+                                            "period".into(),
+                                            token_pos,
+                                        )),
+                                    },
                                     Some(t) => {
-                                        err!(InvalidToken(t, "expected a period".into()))
+                                        err!(InvalidToken(
+                                            t.node,
+                                            "expected a right angle bracket".into(),
+                                            t.pos,
+                                        ))
                                     }
-                                    None => err!(InvalidToken(token, "period".into())),
-                                },
+                                    None => {
+                                        err!(UnexpectedLastToken(
+                                            Token::Dollar,
+                                            "right angle bracket".into(),
+                                            token_pos,
+                                        ))
+                                    }
+                                }
+                            }
+                            // case: $sel<>.trait
+                            Some(Spanned { node: Token::RightAngle, .. }) => match tokens.next() {
+                                Some(Spanned { node: Token::Period, .. }) => Some(String::new()),
                                 Some(t) => {
-                                    err!(InvalidToken(t, "expected a right angle bracket".into()))
+                                    err!(InvalidToken(t.node, "expected a period".into(), t.pos))
                                 }
                                 None => {
-                                    err!(UnexpectedLastToken(token, "right angle bracket".into()))
+                                    err!(UnexpectedLastToken(Token::Dollar, "period".into(), token_pos))
                                 }
                             },
-                            // case: $sel<>.trait
-                            Some(Token::RightAngle) => match tokens.next() {
-                                Some(Token::Period) => Some(String::new()),
-                                Some(t) => err!(InvalidToken(t, "expected a period".into())),
-                                None => err!(UnexpectedLastToken(token, "period".into())),
-                            },
                             Some(t) => {
                                 err!(InvalidToken(
-                                    t,
+                                    t.node,
                                     "expected a string or right angle bracket".into(),
+                                    t.pos,
                                 ))
                             }
                             None => {
                                 err!(UnexpectedLastToken(
-                                    token,
+                                    Token::Dollar,
                                     "string or right angle bracket".into(),
+                                    token_pos,
                                 ))
                             }
                         },
-                        Some(Token::Period) => None,
-                        Some(t) => err!(InvalidToken(t, "expected a period".into())),
-                        None => err!(UnexpectedLastToken(token, "period".into())),
+                        Some(Spanned { node: Token::Period, .. }) => None,
+                        Some(t) => err!(InvalidToken(t.node, "expected a period".into(), t.pos)),
+                        None => err!(UnexpectedLastToken(Token::Dollar, "period".into(), token_pos)),
                     };
 
                     // name of trait for the trait-tag
                     let r#trait: String = match tokens.next() {
-                        Some(Token::Literal(s)) => s,
-                        Some(t) => err!(InvalidToken(t, "expected a string".into())),
-                        None => err!(UnexpectedLastToken(token, "string".into())),
+                        Some(Spanned { node: Token::Ident(s), .. })
+                        | Some(Spanned { node: Token::QuotedString(s), .. }) => s,
+                        Some(t) => err!(InvalidToken(t.node, "expected a string".into(), t.pos)),
+                        None => err!(UnexpectedLastToken(
+                            Token::Dollar,
+                            "string".into(),
+                            token_pos,
+                        )),
                     };
 
-                    Expr::Trait { src, arg, r#trait }
+                    Trait { src, arg, r#trait }
                 }
             };
 
@@ -1,5 +1,109 @@
 use std::fmt;
 
+/// A 1-based source position; `0` in either field means "unknown".
+///
+/// Mirrors the position type used by rhai's tokenizer: code that hasn't
+/// tracked a location yet can hand back `Position::NONE` and downstream
+/// consumers degrade gracefully instead of needing an `Option<Position>`
+/// everywhere.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Position {
+    line: u16,
+    col: u16,
+}
+
+impl Position {
+    pub const NONE: Self = Self { line: 0, col: 0 };
+
+    pub(crate) fn new(line: u16, col: u16) -> Self {
+        Self { line, col }
+    }
+
+    pub fn line(&self) -> Option<u16> {
+        (self.line != 0).then_some(self.line)
+    }
+
+    pub fn col(&self) -> Option<u16> {
+        (self.col != 0).then_some(self.col)
+    }
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.line(), self.col()) {
+            (Some(line), Some(col)) => write!(f, "line {line}, col {col}"),
+            (Some(line), None) => write!(f, "line {line}"),
+            (None, Some(col)) => write!(f, "col {col}"),
+            _ => write!(f, "unknown position"),
+        }
+    }
+}
+
+/// A value paired with the [`Position`] it was lexed from, so a later
+/// conversion failure can report where the offending token actually came
+/// from instead of [`Position::NONE`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spanned<T> {
+    pub node: T,
+    pub pos: Position,
+}
+
+impl<T: fmt::Display> fmt::Display for Spanned<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.node)
+    }
+}
+
+/// A reserved word that a run of identifier characters may promote to,
+/// instead of falling through to a plain [`Token::Ident`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Keyword {
+    If,
+    Elif,
+    Else,
+    End,
+    True,
+    False,
+    And,
+    Or,
+    Not,
+}
+
+impl Keyword {
+    /// Looks up a reserved word by its exact (case-sensitive) spelling.
+    /// Only fires when the whole identifier buffer matches, never a prefix.
+    pub fn lookup(s: &str) -> Option<Self> {
+        Some(match s {
+            "if" => Self::If,
+            "elif" => Self::Elif,
+            "else" => Self::Else,
+            "end" => Self::End,
+            "true" => Self::True,
+            "false" => Self::False,
+            "and" => Self::And,
+            "or" => Self::Or,
+            "not" => Self::Not,
+            _ => return None,
+        })
+    }
+}
+
+impl fmt::Display for Keyword {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::If => write!(f, "if"),
+            Self::Elif => write!(f, "elif"),
+            Self::Else => write!(f, "else"),
+            Self::End => write!(f, "end"),
+            Self::True => write!(f, "true"),
+            Self::False => write!(f, "false"),
+            Self::And => write!(f, "and"),
+            Self::Or => write!(f, "or"),
+            Self::Not => write!(f, "not"),
+        }
+    }
+}
+
 /// A single unit from a line.
 #[derive(Debug, Clone)]
 pub enum Token {
@@ -27,6 +131,20 @@ pub enum Token {
     LessThanEqual,
     NotEqual,
 
+    // Bitwise operators
+    BitAnd,
+    BitOr,
+    BitXor,
+    BitNot,
+    Shl,
+    Shr,
+
+    // Logical operators
+    And,
+    Or,
+
+    /// A reserved word, e.g. `if` or `true`.
+    Keyword(Keyword),
     /// A string explicitly passed as one, using single or double quotes.
     QuotedString(String),
     /// A string that couldn't be parsed as any other symbol.
@@ -39,6 +157,16 @@ pub enum Token {
 /// for example:
 /// - yes: parsing complex tokens which contain primitives, like Attribute
 /// - no:  nesting tokens inside of parentheses
+///
+/// Precedence (loosest to tightest binding), for a later parser stage to
+/// rely on:
+/// 1. `And`/`Or` (short-circuit logical connectives)
+/// 2. relational exprs (`EqualTo`, `LessThanEqual`, `LessThan`, ...)
+/// 3. `BitAnd`/`BitOr`/`BitXor`
+/// 4. `Shl`/`Shr`
+/// 5. `Sub`/`Add`
+/// 6. `Mult`/`Div`/`Mod`
+/// 7. unary `Not`/`BitNot`
 #[derive(Debug, PartialEq)]
 pub enum Expr {
     /// A `key="value"` phrase
@@ -70,6 +198,20 @@ pub enum Expr {
     LessThanEqual,
     NotEqual,
 
+    BitAnd,
+    BitOr,
+    BitXor,
+    BitNot,
+    Shl,
+    Shr,
+
+    /// Short-circuit logical AND (`&&`), distinct from the bitwise `BitAnd`.
+    And,
+    /// Short-circuit logical OR (`||`), distinct from the bitwise `BitOr`.
+    Or,
+    /// Logical negation (`!expr`), distinct from the bitwise `BitNot`.
+    Not,
+
     /// A basic number
     Int(u16),
     /// An explicitly-quoted string literal
@@ -107,6 +249,17 @@ impl fmt::Display for Token {
             Self::LessThanEqual => write!(f, "<="),
             Self::NotEqual => write!(f, "!="),
 
+            Self::BitAnd => write!(f, "&"),
+            Self::BitOr => write!(f, "|"),
+            Self::BitXor => write!(f, "^"),
+            Self::BitNot => write!(f, "~"),
+            Self::Shl => write!(f, "<<"),
+            Self::Shr => write!(f, ">>"),
+
+            Self::And => write!(f, "&&"),
+            Self::Or => write!(f, "||"),
+
+            Self::Keyword(kw) => write!(f, "{kw}"),
             Self::QuotedString(s) => write!(f, "\"{s}\""),
             Self::Ident(s) => write!(f, "{s}"),
         }
@@ -141,6 +294,17 @@ impl fmt::Display for Expr {
             Self::LessThanEqual => write!(f, "<="),
             Self::NotEqual => write!(f, "!="),
 
+            Self::BitAnd => write!(f, "&"),
+            Self::BitOr => write!(f, "|"),
+            Self::BitXor => write!(f, "^"),
+            Self::BitNot => write!(f, "~"),
+            Self::Shl => write!(f, "<<"),
+            Self::Shr => write!(f, ">>"),
+
+            Self::And => write!(f, "&&"),
+            Self::Or => write!(f, "||"),
+            Self::Not => write!(f, "!"),
+
             Self::QuotedString(s) => write!(f, "\"{s}\""),
             Self::Ident(s) => write!(f, "{}", s),
         }
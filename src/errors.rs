@@ -1,5 +1,6 @@
 use core::fmt;
 use std::error::Error;
+use std::ops::Range;
 
 use crate::parsing::Token;
 
@@ -9,12 +10,48 @@ use crate::parsing::Token;
  Provides an interface for easily converting name-value enums
  into `std::error::Error` `impl`s.
 */
-
 pub trait ErrorEnum: fmt::Debug {
     /// Name of an ErrorEnum variant
     fn name(&self) -> String;
     /// Message to display after variant name
     fn message(&self) -> Option<String>;
+    /// Byte-offset range into the source line this error points at, for
+    /// variants that track one. Defaults to `None`.
+    fn span(&self) -> Option<Range<u32>> {
+        None
+    }
+
+    /// Renders this error the way `rustc` would: the usual name/message
+    /// line, followed by `source` with a caret underline beneath the slice
+    /// identified by [`ErrorEnum::span`]. Falls back to the plain
+    /// name/message line if no span is available.
+    fn render_with_source(&self, source: &str) -> String {
+        let header = match self.message() {
+            Some(v) => format!("{}: {}", self.name(), v),
+            None => self.name(),
+        };
+
+        let span = match self.span() {
+            Some(span) => span,
+            None => return header,
+        };
+
+        let start = (span.start as usize).min(source.len());
+        let end = (span.end as usize).clamp(start, source.len());
+        let end = if end == start {
+            (start + 1).min(source.len())
+        } else {
+            end
+        };
+
+        let mut out = header;
+        out.push('\n');
+        out.push_str(source);
+        out.push('\n');
+        out.push_str(&" ".repeat(start));
+        out.push_str(&"^".repeat(end - start));
+        out
+    }
 }
 
 impl fmt::Display for dyn ErrorEnum {
@@ -32,75 +69,150 @@ impl Error for dyn ErrorEnum {}
     Error Types
 */
 
-/// The error result of a failed conversion from RawToken into Token.
+/// The error result of a failed conversion from `&str` into `TokenLine`.
 #[derive(Debug)]
-pub enum TokenUnitConversionError {
-    /// Token not supported for this operation.
-    NotSupported(Token),
-    /// Token not yet supported.
-    ToDo(Token),
+pub enum TokenConversionFailure {
+    /// No values aside from spaces/comments in a string.
+    NoTokensPresent(Range<u32>),
+    /// Inconsistent usage of tabs and spaces.
+    InconsistentWhitespace(Range<u32>),
+    /// Expected a value, but reached end of line instead.
+    /// Name describes the expected character.
+    UnexpectedEol(&'static str, Range<u32>),
+    /// A Unicode character that looks like a piece of ASCII punctuation
+    /// showed up where punctuation is plausible, e.g. a curly quote
+    /// pasted in place of `'`.
+    ConfusableChar {
+        found: char,
+        suggest: char,
+        span: Range<u32>,
+    },
+    /// A `\` escape that isn't one of the recognized forms (`\n`, `\t`,
+    /// `\r`, `\0`, `\\`, `\'`, `\"`, `\xNN`, `\u{...}`). The string is the
+    /// offending sequence, without the leading backslash.
+    InvalidEscape(String, Range<u32>),
+    /// A `]` with no corresponding open bracket before it.
+    UnmatchedCloseBracket(Range<u32>),
+    /// A `[` that was never closed. The span points at the opening bracket.
+    UnmatchedOpenBracket(Range<u32>),
 }
 
-impl ErrorEnum for TokenUnitConversionError {
+impl ErrorEnum for TokenConversionFailure {
     fn name(&self) -> String {
         match self {
-            Self::NotSupported(_) => "NotSupported",
-            Self::ToDo(_) => "ToDo",
+            Self::NoTokensPresent(_) => "NoTokensPresent",
+            Self::InconsistentWhitespace(_) => "InconsistentWhitespace",
+            Self::UnexpectedEol(_, _) => "UnexpectedEol",
+            Self::ConfusableChar { .. } => "ConfusableChar",
+            Self::InvalidEscape(_, _) => "InvalidEscape",
+            Self::UnmatchedCloseBracket(_) => "UnmatchedCloseBracket",
+            Self::UnmatchedOpenBracket(_) => "UnmatchedOpenBracket",
         }
         .to_string()
     }
 
     fn message(&self) -> Option<String> {
         match self {
-            Self::NotSupported(token) => Some(format!("token '{}' not supported", token)),
-            Self::ToDo(token) => Some(format!("token '{}' not yet supported", token)),
+            Self::NoTokensPresent(_) => None,
+            Self::InconsistentWhitespace(_) => {
+                Some("Inconsistent usage of tabs and spaces".to_string())
+            }
+            Self::UnexpectedEol(expected, _) => Some(format!("expected {}, got EOL", expected)),
+            Self::ConfusableChar { found, suggest, .. } => Some(format!(
+                "this character '{found}' (U+{:04X}) looks like '{suggest}'; did you mean '{suggest}'?",
+                *found as u32,
+            )),
+            Self::InvalidEscape(seq, _) => Some(format!("not a valid escape sequence: \\{seq}")),
+            Self::UnmatchedCloseBracket(_) => {
+                Some("']' has no matching '['".to_string())
+            }
+            Self::UnmatchedOpenBracket(_) => Some("'[' is never closed".to_string()),
+        }
+    }
+
+    fn span(&self) -> Option<Range<u32>> {
+        match self {
+            Self::NoTokensPresent(span)
+            | Self::InconsistentWhitespace(span)
+            | Self::UnexpectedEol(_, span)
+            | Self::ConfusableChar { span, .. }
+            | Self::InvalidEscape(_, span)
+            | Self::UnmatchedCloseBracket(span)
+            | Self::UnmatchedOpenBracket(span) => Some(span.clone()),
         }
     }
 }
 
-/// The error result of a failed conversion from &str into Line.
+/// The error result of a failed conversion from `TokenLine` into `ExprLine`.
 #[derive(Debug)]
-pub enum LineConversionError {
-    /// No values aside from spaces/comments in a string.
-    NoTokensPresent,
-    /// Inconsistent usage of tabs and spaces
-    InconsistentWhitespace,
-    /// Expected a value, but reached end of line instead.
-    /// Name describes the expected character.
-    UnexpectedEol(&'static str),
+pub enum ExprConversionFailure {
+    /// Token not usable here. Message describes why.
+    InvalidToken(Token, String, Range<u32>),
+    /// Token not yet supported.
+    ToDo(Token, Range<u32>),
     /// Expected a value, but reached the last token instead.
-    /// Message describes expected tokens.
-    UnexpectedLastToken(&'static str),
-    /// Argument(s) of invalid type.
-    /// Message describes expected type.
-    InvalidArgument(&'static str),
-    /// Failed attempt at converting a standalone `RawToken`.
-    /// Inherited from `Token::TryFrom<RawToken>`.
-    BadTokenUnit(TokenUnitConversionError),
+    /// Message describes the expected token(s).
+    UnexpectedLastToken(Token, String, Range<u32>),
 }
 
-impl ErrorEnum for LineConversionError {
+impl ErrorEnum for ExprConversionFailure {
     fn name(&self) -> String {
         match self {
-            Self::NoTokensPresent => "NoTokensPresent",
-            Self::InconsistentWhitespace => "InconsistentWhitespace",
-            Self::UnexpectedEol(_) => "UnexpectedEol",
-            Self::UnexpectedLastToken(_) => "UnexpectedLastToken",
-            Self::InvalidArgument(_) => "InvalidArgument",
-            Self::BadTokenUnit(t) => return format!("BadTokenUnit::{}", t.name()),
+            Self::InvalidToken(..) => "InvalidToken",
+            Self::ToDo(..) => "ToDo",
+            Self::UnexpectedLastToken(..) => "UnexpectedLastToken",
         }
         .to_string()
     }
 
     fn message(&self) -> Option<String> {
         match self {
-            Self::NoTokensPresent => None,
-            Self::InconsistentWhitespace => {
-                Some("Inconsistent usage of tabs and spaces".to_string())
-            }
-            Self::UnexpectedEol(expected) => Some(format!("expected {}, got EOL", expected)),
-            Self::UnexpectedLastToken(msg) | Self::InvalidArgument(msg) => Some(msg.to_string()),
-            Self::BadTokenUnit(t) => t.message(),
+            Self::InvalidToken(token, msg, _) => Some(format!("{} (token '{}')", msg, token)),
+            Self::ToDo(token, _) => Some(format!("token '{}' not yet supported", token)),
+            Self::UnexpectedLastToken(token, expected, _) => Some(format!(
+                "expected {} after '{}', got end of line",
+                expected, token
+            )),
+        }
+    }
+
+    fn span(&self) -> Option<Range<u32>> {
+        match self {
+            Self::InvalidToken(_, _, span)
+            | Self::ToDo(_, span)
+            | Self::UnexpectedLastToken(_, _, span) => Some(span.clone()),
+        }
+    }
+}
+
+/// The error result of a failed conversion from `&str` into `ExprLine`.
+#[derive(Debug)]
+pub enum LineConversionFailure {
+    /// Failed to tokenize the line in the first place.
+    TokenFailure(TokenConversionFailure),
+    /// Tokenized fine, but failed to convert those tokens into `Expr`s.
+    ExprFailure(ExprConversionFailure),
+}
+
+impl ErrorEnum for LineConversionFailure {
+    fn name(&self) -> String {
+        match self {
+            Self::TokenFailure(e) => format!("TokenFailure::{}", e.name()),
+            Self::ExprFailure(e) => format!("ExprFailure::{}", e.name()),
+        }
+    }
+
+    fn message(&self) -> Option<String> {
+        match self {
+            Self::TokenFailure(e) => e.message(),
+            Self::ExprFailure(e) => e.message(),
+        }
+    }
+
+    fn span(&self) -> Option<Range<u32>> {
+        match self {
+            Self::TokenFailure(e) => e.span(),
+            Self::ExprFailure(e) => e.span(),
         }
     }
 }
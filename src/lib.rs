@@ -3,19 +3,32 @@ mod core;
 #[macro_use]
 pub(crate) mod debug;
 
+// A second, still-experimental tokenizer/expression pipeline built around
+// `Line<T>` and byte-offset spans, sharing no code with `core::lexing`.
+// Not yet wired into `parse_string`.
+pub(crate) mod errors;
+pub(crate) mod parsing;
+
 #[cfg(test)]
 mod tests;
 
 pub(crate) use crate::core::lexing;
-pub use lexing::{Operator, Token, TokenError};
+pub use lexing::{Keyword, Operator, Span, Spanned, Token, TokenError};
 
-pub fn parse_string(s: &str) -> Result<Vec<Token>, TokenError> {
+pub fn parse_string(s: &str) -> Result<Vec<Spanned<Token>>, TokenError> {
     let chars = s.trim_end().chars().peekable();
     lexing::parse_chars(chars)
 }
 
+/// Like [`parse_string`], but collects every [`TokenError`] encountered
+/// instead of stopping at the first one.
+pub fn parse_string_recovering(s: &str) -> (Vec<Spanned<Token>>, Vec<TokenError>) {
+    let chars = s.trim_end().chars().peekable();
+    lexing::parse_chars_recovering(chars)
+}
+
 pub fn render_tokens(
-    tokens: Vec<Token>,
+    tokens: Vec<Spanned<Token>>,
     buf: &mut impl std::io::Write,
 ) -> Result<(), std::io::Error> {
     // use std::iter::Peekable as _;
@@ -24,12 +37,12 @@ pub fn render_tokens(
     while let Some(token) = tokens.next() {
         use Operator::*;
         use Token::*;
-        match token {
+        match token.node {
             Op(NewLine) => {
-                while let Some(_) = tokens.next_if(|t| matches!(t, Indent)) {
+                while let Some(_) = tokens.next_if(|t| matches!(t.node, Indent)) {
                     indent_level += 1;
                 }
-                while let Some(_) = tokens.next_if(|t| matches!(t, Dedent)) {
+                while let Some(_) = tokens.next_if(|t| matches!(t.node, Dedent)) {
                     indent_level -= 1;
                 }
                 writeln!(buf)?;
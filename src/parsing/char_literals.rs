@@ -0,0 +1,25 @@
+//! ASCII punctuation constants used by [`super::lines`]'s tokenizer, mostly
+//! named after the character itself (`COMMENT` is named for its meaning), so
+//! match arms read as `CH::COLON` instead of a bare `':'`.
+
+pub const BACKSLASH: char = '\\';
+pub const COMMENT: char = '#';
+pub const COLON: char = ':';
+pub const PERIOD: char = '.';
+pub const DOLLAR: char = '$';
+
+pub const LEFT_SQUARE: char = '[';
+pub const RIGHT_SQUARE: char = ']';
+pub const FORWARD_SLASH: char = '/';
+pub const ASTERISK: char = '*';
+pub const MINUS: char = '-';
+pub const PLUS: char = '+';
+pub const PERCENTAGE: char = '%';
+
+pub const EQUALS_SIGN: char = '=';
+pub const LEFT_ANGLE: char = '<';
+pub const RIGHT_ANGLE: char = '>';
+pub const BANG: char = '!';
+
+pub const SINGLE_QUOTE: char = '\'';
+pub const DOUBLE_QUOTE: char = '"';
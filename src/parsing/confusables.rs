@@ -0,0 +1,24 @@
+//! Unicode punctuation that is easily mistaken for ASCII punctuation,
+//! e.g. pasted in from a word processor. Grows as new confusables turn up.
+
+/// `(confusable, intended ASCII token)` pairs.
+const TABLE: &[(char, char)] = &[
+    ('\u{2018}', '\''), // LEFT SINGLE QUOTATION MARK
+    ('\u{2019}', '\''), // RIGHT SINGLE QUOTATION MARK
+    ('\u{201C}', '"'),  // LEFT DOUBLE QUOTATION MARK
+    ('\u{201D}', '"'),  // RIGHT DOUBLE QUOTATION MARK
+    ('\u{2013}', '-'),  // EN DASH
+    ('\u{2014}', '-'),  // EM DASH
+    ('\u{FF1C}', '<'),  // FULLWIDTH LESS-THAN SIGN
+    ('\u{FF1E}', '>'),  // FULLWIDTH GREATER-THAN SIGN
+    ('\u{00D7}', '*'),  // MULTIPLICATION SIGN
+];
+
+/// Returns the ASCII punctuation `found` was probably meant to be, if it's
+/// a known confusable.
+pub fn lookup(found: char) -> Option<char> {
+    TABLE
+        .iter()
+        .find(|(confusable, _)| *confusable == found)
+        .map(|(_, suggest)| *suggest)
+}
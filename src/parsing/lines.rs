@@ -1,13 +1,12 @@
 use core::fmt;
+use std::ops::Range;
 
 use crate::{
-    errors::{ExprConversionFailure, LineConversionFailure, TokenConversionFailure},
+    errors::{ErrorEnum, ExprConversionFailure, LineConversionFailure, TokenConversionFailure},
     parsing::RelationalOperator,
 };
 
-use super::{Expr, Token};
-#[cfg(debug_assertions)]
-use crate::debug;
+use super::{ArithmeticOperator, Expr, Spanned, Token};
 
 /// A single line.
 /// Usually should be either Line<Token> or Line<Expr>
@@ -19,28 +18,35 @@ pub struct Line<T> {
     pub(crate) members: Vec<T>,
 }
 
-pub(crate) type TokenLine = Line<Token>;
-pub type ExprLine = Line<Expr>;
+pub(crate) type TokenLine = Line<Spanned<Token>>;
+pub type ExprLine = Line<Spanned<Expr>>;
 
 impl TryFrom<&str> for TokenLine {
     type Error = TokenConversionFailure;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
         use super::char_literals as CH;
+        use super::confusables;
         use super::ArithmeticOperator as AT;
         use TokenConversionFailure::*;
 
         // strip ending whitespace
         let mut line = value.trim_end().chars();
+        // byte offset into `value` just past the character last read by `next_ch_or!`
+        let mut pos: u32 = 0;
 
         /// gets the next value of `ch` OR executes the block statement.
+        /// Advances `pos` by the byte length of the character it reads.
         ///
         /// If given any expression other than a block, it is assumed to
         /// be a LineConversionError variant, and the function exits early.
         macro_rules! next_ch_or {
             ($s:block) => {
                 match line.next() {
-                    Some(ch) => ch,
+                    Some(ch) => {
+                        pos += ch.len_utf8() as u32;
+                        ch
+                    }
                     None => $s,
                 }
             };
@@ -51,46 +57,135 @@ impl TryFrom<&str> for TokenLine {
             };
         }
 
-        let mut ch = next_ch_or!(NoTokensPresent);
+        let mut ch = next_ch_or!(NoTokensPresent(0..0));
 
         // loop over the first couple characters and check for whitespace total/consistency
         let whitespace_char = ch;
         let mut leading_whitespace: u8 = 0;
         while ch.is_whitespace() {
+            let char_start = pos - ch.len_utf8() as u32;
             if ch != whitespace_char {
-                return Err(InconsistentWhitespace);
+                return Err(InconsistentWhitespace(char_start..pos));
             }
             leading_whitespace += 1;
-            ch = next_ch_or!(NoTokensPresent);
+            ch = next_ch_or!(NoTokensPresent(0..pos));
         }
 
         // do work now that the first non-whitespace character is known
         let tokens = {
             use std::fmt::Write;
-            let mut raw_tokens: Vec<Token> = vec![];
+            let mut raw_tokens: Vec<Spanned<Token>> = vec![];
             let mut buf: String = String::new();
+            let mut buf_start: Option<u32> = None;
+            // byte offset where `ch` (the character about to be matched) begins
+            let mut tok_start = pos - ch.len_utf8() as u32;
 
             macro_rules! write_buf {
-                ($($arg:tt)*) => {
+                ($($arg:tt)*) => {{
+                    if buf.is_empty() {
+                        buf_start = Some(tok_start);
+                    }
                     // pretty sure this shouldn´t panic but we´ll see
                     write!(buf, $($arg)*).expect("writing to buffer")
-                };
+                }};
             }
             macro_rules! flush_buf {
-                ($($arg:expr)*) => {{
-                    if buf.len() > 0 {
-                        #[cfg(debug_assertions)] debug!("Pushing token: String({:?})", buf);
-                        raw_tokens.push(Token::String(buf));
-                        #[allow(unused_assignments)] { buf = String::new() };
+                ($boundary:expr) => {{
+                    if !buf.is_empty() {
+                        let taken = std::mem::take(&mut buf);
+                        let start = buf_start.take().expect("buf_start set alongside buf");
+                        #[cfg(debug_assertions)] debug!("Pushing token: String({:?})", taken);
+                        raw_tokens.push(Spanned::new(Token::String(taken), start, $boundary));
                     }
-                    $(
-                        // avoids ownership & cloning issues by computing the value
-                        // before subsequent usage
-                        let arg = $arg;
-                        #[cfg(debug_assertions)] debug!("Pushing token: {:?}", arg);
-                        raw_tokens.push(arg);
-                    )*
+                }};
+                ($boundary:expr, $tok:expr, $end:expr) => {{
+                    flush_buf!($boundary);
+                    let tok = $tok;
+                    #[cfg(debug_assertions)] debug!("Pushing token: {:?}", tok);
+                    raw_tokens.push(Spanned::new(tok, $boundary, $end));
+                }};
+            }
 
+            /// Decodes a backslash escape. Assumes the leading `\` has
+            /// already been consumed, and reads however many characters
+            /// the escape needs.
+            macro_rules! decode_escape {
+                () => {{
+                    let escape_start = pos;
+                    match next_ch_or!(InvalidEscape(String::new(), escape_start..pos)) {
+                        'n' => '\n',
+                        't' => '\t',
+                        'r' => '\r',
+                        '0' => '\0',
+                        '\\' => '\\',
+                        '\'' => '\'',
+                        '"' => '"',
+                        'x' => {
+                            let mut hex = String::new();
+                            for _ in 0..2 {
+                                let c =
+                                    next_ch_or!(InvalidEscape(format!("x{hex}"), escape_start..pos));
+                                if !c.is_ascii_hexdigit() {
+                                    return Err(InvalidEscape(
+                                        format!("x{hex}{c}"),
+                                        escape_start..pos,
+                                    ));
+                                }
+                                hex.push(c);
+                            }
+                            let value =
+                                u8::from_str_radix(&hex, 16).expect("checked ascii hex digits");
+                            if value > 0x7F {
+                                return Err(InvalidEscape(format!("x{hex}"), escape_start..pos));
+                            }
+                            value as char
+                        }
+                        'u' => {
+                            match next_ch_or!(InvalidEscape("u".into(), escape_start..pos)) {
+                                '{' => {}
+                                c => {
+                                    return Err(InvalidEscape(
+                                        format!("u{c}"),
+                                        escape_start..pos,
+                                    ))
+                                }
+                            }
+                            let mut hex = String::new();
+                            loop {
+                                let c = next_ch_or!(InvalidEscape(
+                                    format!("u{{{hex}"),
+                                    escape_start..pos
+                                ));
+                                if c == '}' {
+                                    break;
+                                }
+                                if !c.is_ascii_hexdigit() || hex.len() >= 6 {
+                                    return Err(InvalidEscape(
+                                        format!("u{{{hex}{c}"),
+                                        escape_start..pos,
+                                    ));
+                                }
+                                hex.push(c);
+                            }
+                            if hex.is_empty() {
+                                return Err(InvalidEscape("u{}".into(), escape_start..pos));
+                            }
+                            let value =
+                                u32::from_str_radix(&hex, 16).expect("checked ascii hex digits");
+                            match value {
+                                0xD800..=0xDFFF | 0x110000.. => {
+                                    return Err(InvalidEscape(
+                                        format!("u{{{hex}}}"),
+                                        escape_start..pos,
+                                    ))
+                                }
+                                _ => char::from_u32(value).expect("checked scalar range"),
+                            }
+                        }
+                        other => {
+                            return Err(InvalidEscape(format!("{other}"), escape_start..pos))
+                        }
+                    }
                 }};
             }
 
@@ -100,60 +195,61 @@ impl TryFrom<&str> for TokenLine {
                 /// Defines a token that may take up two characters.
                 macro_rules! composite_token {
                     ($default:expr, $($key:pat, $type:expr)+) => {{
+                        let single_end = tok_start + ch.len_utf8() as u32;
                         let token = 'token: {
                             let next_ch = next_ch_or!({break 'token $default});
                             match next_ch {
                                 // here arise composite tokens
                                 $($key => $type,)+
-                                // parse backslashes as escape chars
+                                // parse backslashes as escape chars, same as
+                                // the bare-token and quoted-literal paths
                                 CH::BACKSLASH => {
-                                    flush_buf!($default);
-                                    if next_ch == CH::BACKSLASH {
-                                        write_buf!("\\");
-                                        ch = next_ch_or!({break 'outer});
-                                    } else {
-                                        ch = next_ch;
-                                    }
+                                    flush_buf!(tok_start, $default, single_end);
+                                    tok_start = single_end;
+                                    write_buf!("{}", decode_escape!());
+                                    ch = next_ch_or!({break 'outer});
+                                    tok_start = pos - ch.len_utf8() as u32;
                                     continue 'outer;
                                 }
                                 // parse other kinds of characters as if they were normal
                                 _ => {
-                                    flush_buf!($default);
+                                    flush_buf!(tok_start, $default, single_end);
                                     ch = next_ch;
+                                    tok_start = pos - ch.len_utf8() as u32;
                                     continue 'outer;
                                 }
                             }
                         };
-                        flush_buf!(token);
+                        flush_buf!(tok_start, token, pos);
                     }};
                 }
 
                 // Delimit at whitespace
                 if ch.is_whitespace() {
-                    flush_buf!();
-                    ch = next_ch_or!({ break })
+                    flush_buf!(tok_start);
+                    ch = next_ch_or!({ break });
+                    tok_start = pos - ch.len_utf8() as u32;
                 };
 
                 match ch {
                     // Escape next character
                     CH::BACKSLASH => {
-                        ch = next_ch_or!(UnexpectedEol("char after backslash"));
-                        write_buf!("{}", ch);
+                        write_buf!("{}", decode_escape!());
                     }
                     // Treat as comment
                     CH::COMMENT => break,
                     // Mark the end of a tag, and allow in-lining afterwards
-                    CH::COLON => flush_buf!(Token::Colon),
+                    CH::COLON => flush_buf!(tok_start, Token::Colon, pos),
                     // `me().attr` trait-tags
-                    CH::PERIOD => flush_buf!(Token::Period),
-                    CH::RIGHT_SQUARE => flush_buf!(Token::Arithmetic(AT::CloseBracket)),
-                    CH::LEFT_SQUARE => flush_buf!(Token::Arithmetic(AT::OpenBracket)),
-                    CH::FORWARD_SLASH => flush_buf!(Token::Arithmetic(AT::Div)),
-                    CH::ASTERISK => flush_buf!(Token::Arithmetic(AT::Mult)),
-                    CH::MINUS => flush_buf!(Token::Arithmetic(AT::Sub)),
-                    CH::PLUS => flush_buf!(Token::Arithmetic(AT::Add)),
-                    CH::PERCENTAGE => flush_buf!(Token::Arithmetic(AT::Mod)),
-                    CH::DOLLAR => flush_buf!(Token::Dollar),
+                    CH::PERIOD => flush_buf!(tok_start, Token::Period, pos),
+                    CH::RIGHT_SQUARE => flush_buf!(tok_start, Token::Arithmetic(AT::CloseBracket), pos),
+                    CH::LEFT_SQUARE => flush_buf!(tok_start, Token::Arithmetic(AT::OpenBracket), pos),
+                    CH::FORWARD_SLASH => flush_buf!(tok_start, Token::Arithmetic(AT::Div), pos),
+                    CH::ASTERISK => flush_buf!(tok_start, Token::Arithmetic(AT::Mult), pos),
+                    CH::MINUS => flush_buf!(tok_start, Token::Arithmetic(AT::Sub), pos),
+                    CH::PLUS => flush_buf!(tok_start, Token::Arithmetic(AT::Add), pos),
+                    CH::PERCENTAGE => flush_buf!(tok_start, Token::Arithmetic(AT::Mod), pos),
+                    CH::DOLLAR => flush_buf!(tok_start, Token::Dollar, pos),
                     // `key="value"` attribute tags
                     CH::EQUALS_SIGN => composite_token!(
                         Token::Equals,
@@ -179,28 +275,41 @@ impl TryFrom<&str> for TokenLine {
                     CH::SINGLE_QUOTE | CH::DOUBLE_QUOTE => {
                         let quote = ch;
                         loop {
-                            ch = next_ch_or!(UnexpectedEol("closing quote"));
+                            ch = next_ch_or!(UnexpectedEol("closing quote", tok_start..pos));
                             if ch == quote {
                                 break;
                             } else if ch == CH::BACKSLASH {
-                                ch = next_ch_or!(UnexpectedEol("char after backslash"));
+                                write_buf!("{}", decode_escape!());
+                                continue;
                             };
                             write_buf!("{}", ch);
                         }
                     }
-                    // Add unrecognized chars to buffer
-                    other => write_buf!("{}", other),
+                    // Flag punctuation look-alikes instead of silently
+                    // swallowing them into a `Token::String`
+                    other => match confusables::lookup(other) {
+                        Some(suggest) => {
+                            return Err(ConfusableChar {
+                                found: other,
+                                suggest,
+                                span: tok_start..pos,
+                            })
+                        }
+                        // Add unrecognized chars to buffer
+                        None => write_buf!("{}", other),
+                    },
                 };
 
-                ch = next_ch_or!({ break })
+                ch = next_ch_or!({ break });
+                tok_start = pos - ch.len_utf8() as u32;
             }
-            flush_buf!();
+            flush_buf!(pos);
 
             raw_tokens
         };
 
         match tokens.len() {
-            0 => Err(NoTokensPresent),
+            0 => Err(NoTokensPresent(0..pos)),
             _ => Ok(Line {
                 total_whitespace: leading_whitespace,
                 members: tokens,
@@ -209,6 +318,33 @@ impl TryFrom<&str> for TokenLine {
     }
 }
 
+impl TokenLine {
+    /// Checks that every `[`/`]` in this line is balanced. Fails on the
+    /// first unmatched `]`; a `[` still open once the line runs out fails
+    /// with a span pointing back at that opening bracket.
+    pub(crate) fn check_delimiters(&self) -> Result<(), TokenConversionFailure> {
+        use TokenConversionFailure::*;
+
+        let mut open: Vec<Range<u32>> = vec![];
+        for member in &self.members {
+            match &member.node {
+                Token::Arithmetic(ArithmeticOperator::OpenBracket) => {
+                    open.push(member.start..member.end)
+                }
+                Token::Arithmetic(ArithmeticOperator::CloseBracket) if open.pop().is_none() => {
+                    return Err(UnmatchedCloseBracket(member.start..member.end));
+                }
+                _ => {}
+            }
+        }
+
+        match open.into_iter().next() {
+            Some(span) => Err(UnmatchedOpenBracket(span)),
+            None => Ok(()),
+        }
+    }
+}
+
 impl TryFrom<TokenLine> for ExprLine {
     type Error = ExprConversionFailure;
 
@@ -235,6 +371,9 @@ impl TryFrom<TokenLine> for ExprLine {
 
         'expr_loop: loop {
             let resp = &mut resp.members;
+            let start = token.start;
+            let end = token.end;
+            let span = start..end;
 
             macro_rules! err {
                 ($e:expr) => {{
@@ -248,27 +387,25 @@ impl TryFrom<TokenLine> for ExprLine {
             }
 
             macro_rules! push {
-                () => {
-                    push!(token);
-                };
-                ($expr:expr) => {
-                    let push_res = $expr;
+                ($spanned:expr) => {{
+                    let push_res = $spanned;
                     #[cfg(debug_assertions)]
                     {
                         debug!("Pushing expression: {:?}", push_res);
                     }
                     resp.push(push_res);
-                };
+                }};
             }
 
-            let expr = match token {
+            let expr = match token.node {
                 Token::Equals | Token::Period => {
                     err!(InvalidToken(
-                        token.to_owned(),
+                        token.node.to_owned(),
                         "Incorrect token to start expression".into(),
+                        span,
                     ))
                 }
-                Token::Bang => err!(ToDo(token.to_owned())),
+                Token::Bang => err!(ToDo(token.node.to_owned(), span)),
                 Token::Colon => Colon,
                 Token::Relational(op) => Relational((&op).into()),
                 Token::Arithmetic(t) => Arithmetic(t),
@@ -276,13 +413,27 @@ impl TryFrom<TokenLine> for ExprLine {
                 Token::RightAngle => Relational(GreaterThan),
                 Token::String(s) => match tokens.next() {
                     // handling for name='attr' expressions
-                    Some(Token::Equals) => {
+                    Some(Spanned {
+                        node: Token::Equals,
+                        ..
+                    }) => {
                         let val = match tokens.next() {
-                            Some(Token::String(s)) => s,
+                            Some(Spanned {
+                                node: Token::String(s),
+                                ..
+                            }) => s,
                             Some(t) => {
-                                err!(InvalidToken(t, "expected string after equals sign".into()))
+                                err!(InvalidToken(
+                                    t.node,
+                                    "expected string after equals sign".into(),
+                                    t.start..t.end,
+                                ))
                             }
-                            None => err!(UnexpectedLastToken(Token::String(s), "string".into())),
+                            None => err!(UnexpectedLastToken(
+                                Token::String(s),
+                                "string".into(),
+                                span,
+                            )),
                         };
                         Attribute { key: s, val }
                     }
@@ -295,10 +446,14 @@ impl TryFrom<TokenLine> for ExprLine {
                     // token, we will handle the first token before trying again
                     // with parsing the second
                     Some(t) => {
-                        push!(match s.parse() {
-                            Ok(n) => Int(n),
-                            Err(_) => Raw(s),
-                        });
+                        push!(Spanned::new(
+                            match s.parse() {
+                                Ok(n) => Int(n),
+                                Err(_) => Raw(s),
+                            },
+                            start,
+                            end,
+                        ));
                         token = t;
                         continue 'expr_loop;
                     }
@@ -307,66 +462,134 @@ impl TryFrom<TokenLine> for ExprLine {
                 Token::Dollar => {
                     // the object or selector
                     let src: String = match tokens.next() {
-                        Some(Token::String(s)) => s,
-                        Some(t) => err!(InvalidToken(t, "expected a string".into())),
-                        None => err!(UnexpectedLastToken(token, "string literal".into())),
+                        Some(Spanned {
+                            node: Token::String(s),
+                            ..
+                        }) => s,
+                        Some(t) => err!(InvalidToken(
+                            t.node,
+                            "expected a string".into(),
+                            t.start..t.end,
+                        )),
+                        None => err!(UnexpectedLastToken(
+                            token.node,
+                            "string literal".into(),
+                            span,
+                        )),
                     };
 
                     // argument for the selector or None for the object
                     let arg: Option<String> = match tokens.next() {
-                        Some(Token::LeftAngle) => match tokens.next() {
+                        Some(Spanned {
+                            node: Token::LeftAngle,
+                            ..
+                        }) => match tokens.next() {
                             // case: $sel<...>.trait
-                            Some(Token::String(s)) => match tokens.next() {
-                                Some(Token::RightAngle) => match tokens.next() {
-                                    Some(Token::Period) => Some(s),
+                            Some(Spanned {
+                                node: Token::String(s),
+                                ..
+                            }) => match tokens.next() {
+                                Some(Spanned {
+                                    node: Token::RightAngle,
+                                    ..
+                                }) => match tokens.next() {
+                                    Some(Spanned {
+                                        node: Token::Period, ..
+                                    }) => Some(s),
                                     Some(t) => {
-                                        err!(InvalidToken(t, "expected a period".into()))
+                                        err!(InvalidToken(
+                                            t.node,
+                                            "expected a period".into(),
+                                            t.start..t.end,
+                                        ))
                                     }
-                                    None => err!(InvalidToken(token, "period".into())),
+                                    None => err!(InvalidToken(
+                                        token.node,
+                                        "period".into(),
+                                        span,
+                                    )),
                                 },
                                 Some(t) => {
-                                    err!(InvalidToken(t, "expected a right angle bracket".into()))
+                                    err!(InvalidToken(
+                                        t.node,
+                                        "expected a right angle bracket".into(),
+                                        t.start..t.end,
+                                    ))
                                 }
                                 None => {
-                                    err!(UnexpectedLastToken(token, "right angle bracket".into()))
+                                    err!(UnexpectedLastToken(
+                                        token.node,
+                                        "right angle bracket".into(),
+                                        span,
+                                    ))
                                 }
                             },
                             // case: $sel<>.trait
-                            Some(Token::RightAngle) => match tokens.next() {
-                                Some(Token::Period) => Some(String::new()),
-                                Some(t) => err!(InvalidToken(t, "expected a period".into())),
-                                None => err!(UnexpectedLastToken(token, "period".into())),
+                            Some(Spanned {
+                                node: Token::RightAngle,
+                                ..
+                            }) => match tokens.next() {
+                                Some(Spanned {
+                                    node: Token::Period, ..
+                                }) => Some(String::new()),
+                                Some(t) => {
+                                    err!(InvalidToken(
+                                        t.node,
+                                        "expected a period".into(),
+                                        t.start..t.end,
+                                    ))
+                                }
+                                None => err!(UnexpectedLastToken(
+                                    token.node,
+                                    "period".into(),
+                                    span,
+                                )),
                             },
                             Some(t) => {
                                 err!(InvalidToken(
-                                    t,
+                                    t.node,
                                     "expected a string or right angle bracket".into(),
+                                    t.start..t.end,
                                 ))
                             }
                             None => {
                                 err!(UnexpectedLastToken(
-                                    token,
+                                    token.node,
                                     "string or right angle bracket".into(),
+                                    span,
                                 ))
                             }
                         },
-                        Some(Token::Period) => None,
-                        Some(t) => err!(InvalidToken(t, "expected a period".into())),
-                        None => err!(UnexpectedLastToken(token, "period".into())),
+                        Some(Spanned {
+                            node: Token::Period, ..
+                        }) => None,
+                        Some(t) => err!(InvalidToken(
+                            t.node,
+                            "expected a period".into(),
+                            t.start..t.end,
+                        )),
+                        None => err!(UnexpectedLastToken(token.node, "period".into(), span)),
                     };
 
                     // name of trait for the trait-tag
                     let r#trait: String = match tokens.next() {
-                        Some(Token::String(s)) => s,
-                        Some(t) => err!(InvalidToken(t, "expected a string".into())),
-                        None => err!(UnexpectedLastToken(token, "string".into())),
+                        Some(Spanned {
+                            node: Token::String(s),
+                            ..
+                        }) => s,
+                        Some(t) => err!(InvalidToken(
+                            t.node,
+                            "expected a string".into(),
+                            t.start..t.end,
+                        )),
+                        None => err!(UnexpectedLastToken(token.node, "string".into(), span)),
                     };
 
                     Expr::Trait { src, arg, r#trait }
                 }
             };
 
-            push!(expr);
+            push!(Spanned::new(expr, start, end));
             match tokens.next() {
                 Some(t) => token = t,
                 None => break 'expr_loop,
@@ -377,6 +600,318 @@ impl TryFrom<TokenLine> for ExprLine {
     }
 }
 
+impl ExprLine {
+    /// Like `TryFrom<TokenLine>`, but never bails out on the first problem.
+    /// Every failure is recorded in the returned `Vec` (in the order it was
+    /// found) and a sentinel [`Expr::Error`] is pushed into its place, then
+    /// parsing resynchronizes at the next `Token::Colon` or `Token::String`
+    /// and keeps going — so a whole line's worth of mistakes can be reported
+    /// at once instead of one-at-a-time. A bracket imbalance found by
+    /// [`TokenLine::check_delimiters`] is the exception: it is recorded
+    /// first, and its sentinel goes at the front of the line's members.
+    pub fn try_from_recovering(value: TokenLine) -> (Self, Vec<ExprConversionFailure>) {
+        // No star-import over Token to avoid clashes with Expr::*,
+        // and to avoid accidental globs in the future when pattern
+        // matching over it
+        use Expr::*;
+        use ExprConversionFailure::*;
+        use RelationalOperator::*;
+
+        let mut resp = Self {
+            total_whitespace: value.total_whitespace,
+            members: vec![],
+        };
+        let mut errors: Vec<ExprConversionFailure> = vec![];
+
+        // Unlike the strict `TryFrom<&str>` path (which runs this ahead of
+        // conversion and bails immediately), recovery mode can't just bail:
+        // it records the bracket problem as another diagnostic and pushes
+        // an `Expr::Error` sentinel for it, then keeps converting the rest
+        // of the line's tokens as usual.
+        if let Err(e) = value.check_delimiters() {
+            let bad_token = match &e {
+                TokenConversionFailure::UnmatchedCloseBracket(_) => {
+                    Token::Arithmetic(ArithmeticOperator::CloseBracket)
+                }
+                TokenConversionFailure::UnmatchedOpenBracket(_) => {
+                    Token::Arithmetic(ArithmeticOperator::OpenBracket)
+                }
+                _ => unreachable!("check_delimiters only ever returns bracket errors"),
+            };
+            let span = e.span().unwrap_or(0..0);
+            let message = e.message().unwrap_or_default();
+            errors.push(ExprConversionFailure::InvalidToken(
+                bad_token,
+                message,
+                span.clone(),
+            ));
+            resp.members
+                .push(Spanned::new(Expr::Error, span.start, span.end));
+        }
+
+        let mut tokens = value.members.into_iter();
+        let mut token = match tokens.next() {
+            Some(t) => t,
+            // If there are no tokens in the vec, we can just return
+            // an empty response
+            None => return (resp, errors),
+        };
+
+        'expr_loop: loop {
+            let resp = &mut resp.members;
+            let start = token.start;
+            let end = token.end;
+            let span = start..end;
+
+            // Unlike the strict `TryFrom`, this doesn't bail out: it
+            // records the error, pushes an `Expr::Error` sentinel in its
+            // place, then fast-forwards `tokens` to the next plausible
+            // expression boundary and resumes `'expr_loop` there.
+            macro_rules! err {
+                ($e:expr) => {{
+                    // grabbed before `$e` runs, since some arms move `span`
+                    // into the error they construct
+                    let fallback_span = span.clone();
+                    let err = $e;
+                    #[cfg(debug_assertions)]
+                    {
+                        debug!("Recovering from error: {err:?}")
+                    }
+                    // The failing token can be several `tokens.next()` calls
+                    // deeper than the expression's starting token (e.g. a
+                    // bad token inside a `$src<arg>.trait` phrase), so the
+                    // sentinel's span has to come from the error itself,
+                    // not from the outer `span`.
+                    let err_span = err.span().unwrap_or(fallback_span);
+                    errors.push(err);
+                    resp.push(Spanned::new(Expr::Error, err_span.start, err_span.end));
+                    loop {
+                        match tokens.next() {
+                            Some(t) if matches!(t.node, Token::Colon | Token::String(_)) => {
+                                token = t;
+                                continue 'expr_loop;
+                            }
+                            Some(_) => continue,
+                            None => break 'expr_loop,
+                        }
+                    }
+                }};
+            }
+
+            macro_rules! push {
+                ($spanned:expr) => {{
+                    let push_res = $spanned;
+                    #[cfg(debug_assertions)]
+                    {
+                        debug!("Pushing expression: {:?}", push_res);
+                    }
+                    resp.push(push_res);
+                }};
+            }
+
+            let expr = match token.node {
+                Token::Equals | Token::Period => {
+                    err!(InvalidToken(
+                        token.node.to_owned(),
+                        "Incorrect token to start expression".into(),
+                        span,
+                    ))
+                }
+                Token::Bang => err!(ToDo(token.node.to_owned(), span)),
+                Token::Colon => Colon,
+                Token::Relational(op) => Relational((&op).into()),
+                Token::Arithmetic(t) => Arithmetic(t),
+                Token::LeftAngle => Relational(LessThan),
+                Token::RightAngle => Relational(GreaterThan),
+                Token::String(s) => match tokens.next() {
+                    // handling for name='attr' expressions
+                    Some(Spanned {
+                        node: Token::Equals,
+                        ..
+                    }) => {
+                        let val = match tokens.next() {
+                            Some(Spanned {
+                                node: Token::String(s),
+                                ..
+                            }) => s,
+                            Some(t) => {
+                                err!(InvalidToken(
+                                    t.node,
+                                    "expected string after equals sign".into(),
+                                    t.start..t.end,
+                                ))
+                            }
+                            None => err!(UnexpectedLastToken(
+                                Token::String(s),
+                                "string".into(),
+                                span,
+                            )),
+                        };
+                        Attribute { key: s, val }
+                    }
+                    // with no subsequent token
+                    None => match s.parse() {
+                        Ok(n) => Int(n),
+                        Err(_) => Raw(s),
+                    },
+                    // in the case that the next token is just some irrelevant
+                    // token, we will handle the first token before trying again
+                    // with parsing the second
+                    Some(t) => {
+                        push!(Spanned::new(
+                            match s.parse() {
+                                Ok(n) => Int(n),
+                                Err(_) => Raw(s),
+                            },
+                            start,
+                            end,
+                        ));
+                        token = t;
+                        continue 'expr_loop;
+                    }
+                },
+
+                Token::Dollar => {
+                    // the object or selector
+                    let src: String = match tokens.next() {
+                        Some(Spanned {
+                            node: Token::String(s),
+                            ..
+                        }) => s,
+                        Some(t) => err!(InvalidToken(
+                            t.node,
+                            "expected a string".into(),
+                            t.start..t.end,
+                        )),
+                        None => err!(UnexpectedLastToken(
+                            token.node,
+                            "string literal".into(),
+                            span,
+                        )),
+                    };
+
+                    // argument for the selector or None for the object
+                    let arg: Option<String> = match tokens.next() {
+                        Some(Spanned {
+                            node: Token::LeftAngle,
+                            ..
+                        }) => match tokens.next() {
+                            // case: $sel<...>.trait
+                            Some(Spanned {
+                                node: Token::String(s),
+                                ..
+                            }) => match tokens.next() {
+                                Some(Spanned {
+                                    node: Token::RightAngle,
+                                    ..
+                                }) => match tokens.next() {
+                                    Some(Spanned {
+                                        node: Token::Period, ..
+                                    }) => Some(s),
+                                    Some(t) => {
+                                        err!(InvalidToken(
+                                            t.node,
+                                            "expected a period".into(),
+                                            t.start..t.end,
+                                        ))
+                                    }
+                                    None => err!(InvalidToken(
+                                        token.node,
+                                        "period".into(),
+                                        span,
+                                    )),
+                                },
+                                Some(t) => {
+                                    err!(InvalidToken(
+                                        t.node,
+                                        "expected a right angle bracket".into(),
+                                        t.start..t.end,
+                                    ))
+                                }
+                                None => {
+                                    err!(UnexpectedLastToken(
+                                        token.node,
+                                        "right angle bracket".into(),
+                                        span,
+                                    ))
+                                }
+                            },
+                            // case: $sel<>.trait
+                            Some(Spanned {
+                                node: Token::RightAngle,
+                                ..
+                            }) => match tokens.next() {
+                                Some(Spanned {
+                                    node: Token::Period, ..
+                                }) => Some(String::new()),
+                                Some(t) => {
+                                    err!(InvalidToken(
+                                        t.node,
+                                        "expected a period".into(),
+                                        t.start..t.end,
+                                    ))
+                                }
+                                None => err!(UnexpectedLastToken(
+                                    token.node,
+                                    "period".into(),
+                                    span,
+                                )),
+                            },
+                            Some(t) => {
+                                err!(InvalidToken(
+                                    t.node,
+                                    "expected a string or right angle bracket".into(),
+                                    t.start..t.end,
+                                ))
+                            }
+                            None => {
+                                err!(UnexpectedLastToken(
+                                    token.node,
+                                    "string or right angle bracket".into(),
+                                    span,
+                                ))
+                            }
+                        },
+                        Some(Spanned {
+                            node: Token::Period, ..
+                        }) => None,
+                        Some(t) => err!(InvalidToken(
+                            t.node,
+                            "expected a period".into(),
+                            t.start..t.end,
+                        )),
+                        None => err!(UnexpectedLastToken(token.node, "period".into(), span)),
+                    };
+
+                    // name of trait for the trait-tag
+                    let r#trait: String = match tokens.next() {
+                        Some(Spanned {
+                            node: Token::String(s),
+                            ..
+                        }) => s,
+                        Some(t) => err!(InvalidToken(
+                            t.node,
+                            "expected a string".into(),
+                            t.start..t.end,
+                        )),
+                        None => err!(UnexpectedLastToken(token.node, "string".into(), span)),
+                    };
+
+                    Expr::Trait { src, arg, r#trait }
+                }
+            };
+
+            push!(Spanned::new(expr, start, end));
+            match tokens.next() {
+                Some(t) => token = t,
+                None => break 'expr_loop,
+            };
+        }
+
+        (resp, errors)
+    }
+}
+
 impl TryFrom<&str> for ExprLine {
     type Error = LineConversionFailure;
 
@@ -388,6 +923,10 @@ impl TryFrom<&str> for ExprLine {
             Err(e) => return Err(Error::TokenFailure(e)),
         };
 
+        if let Err(e) = line.check_delimiters() {
+            return Err(Error::TokenFailure(e));
+        }
+
         match ExprLine::try_from(line) {
             Ok(l) => Ok(l),
             Err(e) => Err(Error::ExprFailure(e)),
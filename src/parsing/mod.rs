@@ -1,7 +1,9 @@
 mod char_literals;
+mod confusables;
 mod lines;
 mod structs;
 
-pub use crate::errors::{LineConversionFailure, TokenConversionFailure};
-pub use lines::{ExprLine, Line};
-pub(crate) use structs::{ArithmeticOperator, Expr, RelationalOperator, Token};
+pub use crate::errors::{ExprConversionFailure, TokenConversionFailure};
+pub use lines::ExprLine;
+pub(crate) use lines::TokenLine;
+pub(crate) use structs::{ArithmeticOperator, Expr, RelationalOperator, Spanned, Token};
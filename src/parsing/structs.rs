@@ -1,8 +1,35 @@
 use std::fmt;
+use std::ops::Range;
+
+/// A parsed value paired with the byte-offset range in its source line
+/// that produced it, so error reporting and editor integrations can point
+/// back at exactly the right slice.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spanned<T> {
+    pub node: T,
+    pub start: u32,
+    pub end: u32,
+}
+
+impl<T> Spanned<T> {
+    pub fn new(node: T, start: u32, end: u32) -> Self {
+        Self { node, start, end }
+    }
+
+    pub fn span(&self) -> Range<u32> {
+        self.start..self.end
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Spanned<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.node)
+    }
+}
 
 /// Basic math operators
 #[derive(Debug, PartialEq, Clone)]
-pub enum ArithmeticToken {
+pub enum ArithmeticOperator {
     /// A left square bracket.
     OpenBracket,
     /// A right square bracket.
@@ -30,9 +57,9 @@ pub enum CompositeRelationalOperator {
     NotEqual,
 }
 
-impl Into<RelationalOperator> for &CompositeRelationalOperator {
-    fn into(self) -> RelationalOperator {
-        match self {
+impl From<&CompositeRelationalOperator> for RelationalOperator {
+    fn from(value: &CompositeRelationalOperator) -> Self {
+        match value {
             CompositeRelationalOperator::EqualTo => RelationalOperator::EqualTo,
             CompositeRelationalOperator::GreaterThanEqual => RelationalOperator::GreaterThanEqual,
             CompositeRelationalOperator::LessThanEqual => RelationalOperator::LessThanEqual,
@@ -53,21 +80,8 @@ pub enum RelationalOperator {
     NotEqual,
 }
 
-impl RelationalOperator {
-    fn abbr(&self) -> &'static str {
-        match self {
-            RelationalOperator::EqualTo => "et",
-            RelationalOperator::GreaterThan => "gt",
-            RelationalOperator::GreaterThanEqual => "gte",
-            RelationalOperator::LessThan => "lt",
-            RelationalOperator::LessThanEqual => "lte",
-            RelationalOperator::NotEqual => "ne",
-        }
-    }
-}
-
 /// A single unit from a line.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Token {
     // Literals
     Equals,
@@ -76,10 +90,12 @@ pub enum Token {
     Bang,
     Period,
     Colon,
+    /// A dollar sign, starting a `$selector<arg>.trait` phrase.
+    Dollar,
     /// Relational operators with two characters in length
     Relational(CompositeRelationalOperator),
     /// Basic binary (mostly) operators.
-    Arithmetic(ArithmeticToken),
+    Arithmetic(ArithmeticOperator),
 
     /// A string that couldn't be parsed as any other symbol.
     String(String),
@@ -95,32 +111,67 @@ pub enum Token {
 pub enum Expr {
     /// A `key="value"` phrase
     Attribute { key: String, val: String },
-    /// A `src.trait` phrase
-    Trait { src: String, r#trait: String },
+    /// A `$src<arg>.trait` phrase. `arg` is `None` for the bare
+    /// `$src.trait` form (no selector argument).
+    Trait {
+        src: String,
+        arg: Option<String>,
+        r#trait: String,
+    },
     /// A basic number
     Int(u16),
     /// An uppercase semicolon
     Colon,
     /// A binary arithmetic operator
-    Arithmetic(ArithmeticToken),
+    Arithmetic(ArithmeticOperator),
     /// A binary relational operator
     Relational(RelationalOperator),
     /// Data that couldn't be parsed as any other type
     Raw(String),
+    /// Placeholder for an expression that failed to parse, used by
+    /// [`crate::parsing::ExprLine::try_from_recovering`] so a line with
+    /// multiple mistakes can still report all of them.
+    Error,
 }
 
-impl TryFrom<Token> for Expr {
+impl TryFrom<Spanned<Token>> for Spanned<Expr> {
     type Error = crate::errors::ExprConversionFailure;
-    /// Attempts to convert a RawToken to a Token.
-    /// Does not work for certain types or if
-    fn try_from(value: Token) -> Result<Self, Self::Error> {
+    /// Attempts to convert a spanned Token to a spanned Expr, carrying the
+    /// byte-offset range over unchanged. Does not work for certain types.
+    fn try_from(value: Spanned<Token>) -> Result<Self, Self::Error> {
         use crate::errors::ExprConversionFailure::*;
         use Expr::*;
         use RelationalOperator::*;
-        let resp: Self = match value {
-            Token::Equals => return Err(NotSupported(value)),
-            Token::Period => return Err(NotSupported(value)),
-            Token::Bang => return Err(ToDo(value)),
+
+        let Spanned {
+            node: value,
+            start,
+            end,
+        } = value;
+
+        let resp: Expr = match value {
+            Token::Equals => {
+                return Err(InvalidToken(
+                    value,
+                    "not valid to start an expression".into(),
+                    start..end,
+                ))
+            }
+            Token::Period => {
+                return Err(InvalidToken(
+                    value,
+                    "not valid to start an expression".into(),
+                    start..end,
+                ))
+            }
+            Token::Bang => return Err(ToDo(value, start..end)),
+            Token::Dollar => {
+                return Err(InvalidToken(
+                    value,
+                    "a lone '$' needs a selector and trait to form an expression".into(),
+                    start..end,
+                ))
+            }
             Token::LeftAngle => Relational(LessThan),
             Token::RightAngle => Relational(GreaterThan),
             Token::Colon => Colon,
@@ -131,7 +182,7 @@ impl TryFrom<Token> for Expr {
                 Err(_) => Raw(s),
             },
         };
-        Ok(resp)
+        Ok(Spanned::new(resp, start, end))
     }
 }
 
@@ -140,7 +191,7 @@ impl TryFrom<Token> for Expr {
     internal token structs in the .nox format.
 */
 
-impl fmt::Display for ArithmeticToken {
+impl fmt::Display for ArithmeticOperator {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
@@ -184,7 +235,11 @@ impl fmt::Display for Token {
             Self::Bang => write!(f, "!"),
             Self::Period => write!(f, "."),
             Self::Colon => write!(f, ":"),
-            Self::Relational(r) => write!(f, "{}", &RelationalOperator::from(r.into())),
+            Self::Dollar => write!(f, "$"),
+            Self::Relational(r) => {
+                let op: RelationalOperator = r.into();
+                write!(f, "{}", op)
+            }
             Self::Arithmetic(a) => write!(f, "{}", a),
             Self::String(s) => write!(f, "{}", s),
         }
@@ -195,12 +250,16 @@ impl fmt::Display for Expr {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match &self {
             Expr::Attribute { key, val } => write!(f, "{}=\"{}\"", key, val),
-            Expr::Trait { src, r#trait } => write!(f, "{}.{}", src, r#trait),
+            Expr::Trait { src, arg, r#trait } => match arg {
+                Some(arg) => write!(f, "${}<{}>.{}", src, arg, r#trait),
+                None => write!(f, "${}.{}", src, r#trait),
+            },
             Expr::Int(i) => write!(f, "{}", i),
             Expr::Colon => write!(f, ":"),
             Expr::Arithmetic(op) => write!(f, "{}", op),
             Expr::Raw(s) => write!(f, "{}", s),
             Expr::Relational(r) => write!(f, "{}", r),
+            Expr::Error => write!(f, "<error>"),
         }
     }
 }
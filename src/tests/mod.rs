@@ -2,7 +2,11 @@ use rstest::rstest;
 
 use crate::Operator::*;
 use crate::Token::*;
-use crate::*;
+// Not `use crate::*`: the crate root also has a private `core` module, and a
+// glob import re-exporting it as the unqualified name `core` shadows the
+// `core` crate itself for anything in this file that expands to bare
+// `core::...` paths (e.g. `#[rstest]`'s generated code).
+use crate::{parse_string, parse_string_recovering, Token};
 
 #[rstest]
 #[case("#This line should be empty.", vec![])]
@@ -30,7 +34,125 @@ use crate::*;
     Number(6.0), Op(Bang),
     Number(7.0), Op(NotEqual),
 ])]
+#[case("1 & 2 | 3 ^ 4 ~5 << 6 >> 7", vec![
+    Number(1.0), Op(BitAnd),
+    Number(2.0), Op(BitOr),
+    Number(3.0), Op(BitXor),
+    Number(4.0), Op(BitNot), Number(5.0), Op(Shl),
+    Number(6.0), Op(Shr),
+    Number(7.0),
+])]
+#[case("0xFF 0b101 0o17", vec![Int(255), Int(5), Int(15)])]
+#[case("if true and not false", vec![
+    Keyword(crate::Keyword::If),
+    Keyword(crate::Keyword::True),
+    Keyword(crate::Keyword::And),
+    Keyword(crate::Keyword::Not),
+    Keyword(crate::Keyword::False),
+])]
+#[case("iffy", vec![Identifier("iffy".into())])]
+#[case("1 && 2 || !3", vec![
+    Number(1.0), Op(And),
+    Number(2.0), Op(Or),
+    Op(Bang), Number(3.0),
+])]
 fn general_lexing(#[case] line: &str, #[case] expected: Vec<Token>) {
     let value = parse_string(line).expect("should yield expressions");
+    let value: Vec<Token> = value.into_iter().map(|spanned| spanned.node).collect();
     assert_eq!(value, expected);
 }
+
+#[rstest]
+// a single bad char shouldn't swallow the tokens lexed after it
+#[case("1 @ 2 @ 3", vec![Number(1.0), Number(2.0), Number(3.0)], 2)]
+// an unterminated string runs out at end-of-input, so nothing follows it
+#[case("1 \"oops", vec![Number(1.0)], 1)]
+fn recovering_lexing(
+    #[case] line: &str,
+    #[case] expected: Vec<Token>,
+    #[case] expected_error_count: usize,
+) {
+    let (tokens, errors) = parse_string_recovering(line);
+    let tokens: Vec<Token> = tokens.into_iter().map(|spanned| spanned.node).collect();
+    assert_eq!(tokens, expected);
+    assert_eq!(errors.len(), expected_error_count);
+}
+
+// The `Line<T>`/byte-offset-span pipeline under `crate::parsing`, a second
+// tokenizer/expression pipeline distinct from `core::lexing` above.
+use crate::errors::{ErrorEnum, LineConversionFailure};
+use crate::parsing::{
+    ArithmeticOperator, ExprConversionFailure, Expr, ExprLine, Token as PToken, TokenConversionFailure,
+    TokenLine,
+};
+
+#[test]
+fn expr_line_spans_point_at_source() {
+    let line = ExprLine::try_from("12 [3]").expect("should parse");
+    assert_eq!(line.members[0].node, Expr::Int(12));
+    assert_eq!(line.members[0].span(), 0..2);
+    assert_eq!(line.members[1].span(), 3..4);
+}
+
+#[rstest]
+#[case("key=\"value\"", vec![PToken::String("key".into()), PToken::Equals, PToken::String("value".into())])]
+// bare-token escape path
+#[case("a\\nb", vec![PToken::String("a\nb".into())])]
+// quoted-literal escape path
+#[case("'it\\'s'", vec![PToken::String("it's".into())])]
+// composite-operator escape path: a backslash immediately following `=`
+// should decode the same as the other two paths, not write a literal `\`
+#[case("=\\n", vec![PToken::Equals, PToken::String("\n".into())])]
+fn token_line_lexing(#[case] line: &str, #[case] expected: Vec<PToken>) {
+    let line = TokenLine::try_from(line).expect("should tokenize");
+    let tokens: Vec<PToken> = line.members.into_iter().map(|spanned| spanned.node).collect();
+    assert_eq!(tokens, expected);
+}
+
+#[rstest]
+#[case('\u{2019}', '\'')] // RIGHT SINGLE QUOTATION MARK
+#[case('\u{2014}', '-')] // EM DASH
+fn confusable_char_detected(#[case] found: char, #[case] suggest: char) {
+    let line = found.to_string();
+    let err = TokenLine::try_from(line.as_str()).expect_err("should flag confusable char");
+    match err {
+        TokenConversionFailure::ConfusableChar { found: f, suggest: s, .. } => {
+            assert_eq!(f, found);
+            assert_eq!(s, suggest);
+        }
+        other => panic!("expected ConfusableChar, got {other:?}"),
+    }
+}
+
+#[test]
+fn recovering_mode_reports_unbalanced_brackets() {
+    let line = TokenLine::try_from("1 ] 2").expect("tokenizes fine on its own");
+    let (expr_line, errors) = ExprLine::try_from_recovering(line);
+
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        errors[0],
+        ExprConversionFailure::InvalidToken(PToken::Arithmetic(ArithmeticOperator::CloseBracket), ..)
+    ));
+    assert_eq!(expr_line.members[0].node, Expr::Error);
+}
+
+#[test]
+fn strict_mode_rejects_unbalanced_brackets() {
+    let source = "1 ]";
+    let err = ExprLine::try_from(source).expect_err("unmatched ']' should fail outright");
+    assert!(matches!(
+        err,
+        LineConversionFailure::TokenFailure(TokenConversionFailure::UnmatchedCloseBracket(_))
+    ));
+    assert_eq!(err.span(), Some(2..3));
+}
+
+#[test]
+fn render_with_source_underlines_the_span() {
+    let source = "1 ]";
+    let err = ExprLine::try_from(source).expect_err("unmatched ']' should fail outright");
+    let rendered = err.render_with_source(source);
+    assert!(rendered.contains("UnmatchedCloseBracket"));
+    assert!(rendered.ends_with("  ^"));
+}